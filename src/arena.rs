@@ -1,3 +1,5 @@
+use bevy::utils::HashMap;
+
 use crate::prelude::*;
 
 pub const ARENA_WIDTH: f32 = 1280.0;
@@ -5,7 +7,6 @@ pub const ARENA_HEIGHT: f32 = 800.0;
 
 #[derive(Debug, Resource)]
 pub struct Arena {
-    pub asteroid_spawn_timer: Timer,
     pub score: u32,
 }
 
@@ -19,40 +20,58 @@ impl Plugin for ArenaPlugin {
 }
 
 fn spawn_arena(mut commands: Commands, mut rapier_configuration: ResMut<RapierConfiguration>) {
-    commands.insert_resource(Arena {
-        asteroid_spawn_timer: Timer::from_seconds(5.0, TimerMode::Once),
-        score: 0,
-    });
+    commands.insert_resource(Arena { score: 0 });
 
     // Rapier configuration without gravity
     rapier_configuration.gravity = Vec2::ZERO;
 }
 
-fn movement(mut query: Query<(&Velocity, &mut Transform)>) {
-    for (velocity, mut transform) in query.iter_mut() {
-        let mut x = transform.translation.x;
-        let mut y = transform.translation.y;
-        let mut updated = false;
-        // Wrap around screen edges
-        let half_width = ARENA_WIDTH / 2.0;
-        let half_height = ARENA_HEIGHT / 2.0;
+// Wrap around screen edges.
+// Sections and towed loads jump along with their ship, so that no joint is stretched.
+fn movement(
+    mut bodies: Query<(Entity, &Velocity, &mut Transform)>,
+    sections: Query<&ShipSection>,
+    towed: Query<&Towed>,
+    parents: Query<&Parent>,
+) {
+    let root_of = |entity: Entity| {
+        let body = towed
+            .get(entity)
+            .ok()
+            .and_then(|towed| parents.get(towed.tractor).ok())
+            .map_or(entity, |parent| parent.get());
+        sections.get(body).map_or(body, |section| section.ship)
+    };
+    let half_width = ARENA_WIDTH / 2.0;
+    let half_height = ARENA_HEIGHT / 2.0;
+    let mut offsets = HashMap::new();
+    for (entity, velocity, transform) in bodies.iter() {
+        if root_of(entity) != entity {
+            continue;
+        }
+        let x = transform.translation.x;
+        let y = transform.translation.y;
+        let mut offset = Vec2::ZERO;
         if x < -half_width && velocity.linvel.x < 0.0 {
-            x = half_width;
-            updated = true;
+            offset.x = ARENA_WIDTH;
         } else if x > half_width && velocity.linvel.x > 0.0 {
-            x = -half_width;
-            updated = true;
+            offset.x = -ARENA_WIDTH;
         }
         if y < -half_height && velocity.linvel.y < 0.0 {
-            y = half_height;
-            updated = true;
+            offset.y = ARENA_HEIGHT;
         } else if y > half_height && velocity.linvel.y > 0.0 {
-            y = -half_height;
-            updated = true;
+            offset.y = -ARENA_HEIGHT;
+        }
+        if offset != Vec2::ZERO {
+            offsets.insert(entity, offset);
         }
-        if updated {
-            transform.translation.x = x;
-            transform.translation.y = y;
+    }
+    if offsets.is_empty() {
+        return;
+    }
+    for (entity, _, mut transform) in bodies.iter_mut() {
+        if let Some(offset) = offsets.get(&root_of(entity)) {
+            transform.translation += offset.extend(0.0);
         }
     }
 }
@@ -1,22 +1,52 @@
 use crate::prelude::*;
 
+/// Preloaded sprite a weapon definition fires its projectiles with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProjectileSprite {
+    Laser,
+    Flare,
+}
+
+/// Preloaded sound a weapon definition plays on every shot
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponSound {
+    Laser,
+    Blast,
+}
+
 #[derive(Debug, Resource)]
 pub struct SpriteAssets {
     pub laser: Handle<Image>,
-    pub meteor_big: Handle<Image>,
-    pub meteor_med: Handle<Image>,
-    pub meteor_small: Handle<Image>,
-    pub player_ship: Handle<Image>,
+    pub flare: Handle<Image>,
     pub ship_explosion: Handle<Image>,
     pub ship_contact: Handle<Image>,
 }
 #[derive(Debug, Resource)]
 pub struct AudioAssets {
     pub laser_trigger: Handle<AudioSource>,
+    pub blast_trigger: Handle<AudioSource>,
     pub ship_explosion: Handle<AudioSource>,
     pub ship_contact: Handle<AudioSource>,
 }
 
+impl SpriteAssets {
+    pub fn projectile(&self, sprite: ProjectileSprite) -> Handle<Image> {
+        match sprite {
+            ProjectileSprite::Laser => self.laser.clone(),
+            ProjectileSprite::Flare => self.flare.clone(),
+        }
+    }
+}
+
+impl AudioAssets {
+    pub fn weapon(&self, sound: WeaponSound) -> Handle<AudioSource> {
+        match sound {
+            WeaponSound::Laser => self.laser_trigger.clone(),
+            WeaponSound::Blast => self.blast_trigger.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Resource)]
 pub struct UiAssets {
     pub font: Handle<Font>,
@@ -34,15 +64,13 @@ impl Plugin for AssetsPlugin {
 fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(SpriteAssets {
         laser: asset_server.load("laserRed07.png"),
-        meteor_big: asset_server.load("meteorBrown_big1.png"),
-        meteor_med: asset_server.load("meteorBrown_med1.png"),
-        meteor_small: asset_server.load("meteorBrown_small1.png"),
-        player_ship: asset_server.load("playerShip2_red.png"),
+        flare: asset_server.load("flash00.png"),
         ship_explosion: asset_server.load("explosion01.png"),
         ship_contact: asset_server.load("explosion01.png"),
     });
     commands.insert_resource(AudioAssets {
         laser_trigger: asset_server.load("sfx_laser1.ogg"),
+        blast_trigger: asset_server.load("Explosion.ogg"),
         ship_explosion: asset_server.load("Explosion_ship.ogg"),
         ship_contact: asset_server.load("Explosion.ogg"),
     });
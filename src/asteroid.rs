@@ -0,0 +1,393 @@
+use std::f32::consts::PI;
+
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+/// Asteroids are no longer spawned once there are this many in the arena
+pub const MAX_ASTEROIDS: usize = 10;
+/// Mass per px2 of asteroid
+pub const ASTEROID_DENSITY: f32 = 0.004;
+/// Asteroids smaller than this area px2 crumble to dust instead of breaking into fragments
+pub const MIN_ASTEROID_AREA: f32 = 150.0;
+/// Speed px/s at which the two sides of a fracture part from each other
+pub const FRACTURE_SPEED: f32 = 30.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AsteroidSize {
+    Big,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    /// Size class of an asteroid, by area px2
+    pub fn of_area(area: f32) -> Self {
+        if area >= 3000.0 {
+            AsteroidSize::Big
+        } else if area >= 1000.0 {
+            AsteroidSize::Medium
+        } else {
+            AsteroidSize::Small
+        }
+    }
+
+    /// Score earned by breaking the asteroid
+    pub fn score(&self) -> u32 {
+        match self {
+            AsteroidSize::Big => 40,
+            AsteroidSize::Medium => 20,
+            AsteroidSize::Small => 10,
+        }
+    }
+
+    /// Ore pickups left when the asteroid breaks
+    pub fn ore_pickups(&self) -> u32 {
+        match self {
+            AsteroidSize::Big => 3,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Small => 1,
+        }
+    }
+
+    /// Outer radius px of a freshly generated asteroid
+    pub fn radius(&self) -> f32 {
+        match self {
+            AsteroidSize::Big => 45.0,
+            AsteroidSize::Medium => 21.0,
+            AsteroidSize::Small => 14.0,
+        }
+    }
+
+    /// Ramming damage needed to break the asteroid
+    pub fn toughness(&self) -> u32 {
+        match self {
+            AsteroidSize::Big => 3,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Small => 1,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Asteroid {
+    pub size: AsteroidSize,
+    /// Outline in local coordinates, centered on the centroid, counter-clockwise
+    pub polygon: Vec<Vec2>,
+}
+
+/// Sent when an asteroid is shot, or rammed
+pub struct AsteroidSplitEvent {
+    pub asteroid: Entity,
+    /// Point and direction of the fracture line in world coordinates,
+    /// a random line through the centroid when missing
+    pub fracture: Option<(Vec2, Vec2)>,
+}
+
+pub struct AsteroidSpawnEvent {
+    /// Outline relative to the position, counter-clockwise
+    pub polygon: Vec<Vec2>,
+    pub position: Vec2,
+    pub rotation: Quat,
+    pub velocity: Velocity,
+}
+
+pub struct AsteroidPlugin;
+
+impl Plugin for AsteroidPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AsteroidSpawnEvent>()
+            .add_event::<AsteroidSplitEvent>()
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(asteroid_split_system.after(ContactResolution))
+                    .with_system(spawn_asteroid.after(asteroid_split_system)),
+            );
+    }
+}
+
+// Asteroids break along the fracture line, scoring by size and dropping ore.
+// Concave asteroids may break in more than two fragments, sharing the whole area.
+// Each fragment keeps the momentum of its part of the asteroid, asteroids too small crumble.
+fn asteroid_split_system(
+    mut commands: Commands,
+    mut asteroid_split_events: EventReader<AsteroidSplitEvent>,
+    mut arena: ResMut<Arena>,
+    mut asteroid_spawn_events: EventWriter<AsteroidSpawnEvent>,
+    mut ore_spawn_events: EventWriter<OreSpawnEvent>,
+    asteroids: Query<(&Asteroid, &Transform, &Velocity)>,
+) {
+    // An asteroid hit by several projectiles at once only breaks once
+    let mut split = HashSet::default();
+    for event in asteroid_split_events.iter() {
+        if !split.insert(event.asteroid) {
+            continue;
+        }
+        let Ok((asteroid, transform, velocity)) = asteroids.get(event.asteroid) else {
+            continue;
+        };
+        commands.entity(event.asteroid).despawn();
+        arena.score += asteroid.size.score();
+        ore_spawn_events.send(OreSpawnEvent {
+            position: transform.translation.truncate(),
+            velocity: velocity.linvel,
+            pickups: asteroid.size.ore_pickups(),
+        });
+
+        // Fracture line in local coordinates
+        let to_local = transform.compute_matrix().inverse();
+        let (point, direction) = match event.fracture {
+            Some((point, direction)) => (
+                to_local.transform_point3(point.extend(0.0)).truncate(),
+                to_local.transform_vector3(direction.extend(0.0)).truncate(),
+            ),
+            None => (
+                Vec2::ZERO,
+                Vec2::from_angle(thread_rng().gen_range(0.0..2.0 * PI)),
+            ),
+        };
+        let normal = direction.perp().normalize_or_zero();
+        if normal == Vec2::ZERO {
+            continue;
+        }
+        if polygon_area(&asteroid.polygon) < MIN_ASTEROID_AREA {
+            continue;
+        }
+        let sides = [normal, -normal].map(|side| {
+            let fragments = split_polygon(&asteroid.polygon, point, side);
+            let area: f32 = fragments
+                .iter()
+                .map(|fragment| polygon_area(fragment))
+                .sum();
+            (side, fragments, area)
+        });
+        let total = sides[0].2 + sides[1].2;
+        if total <= 0.0 {
+            continue;
+        }
+        for (side, fragments, side_area) in sides.iter() {
+            // Push of each side away from the other, conserving the momentum
+            let push = *side * FRACTURE_SPEED * (total - side_area) / total;
+            for fragment in fragments {
+                let centroid = polygon_centroid(fragment);
+                let offset = transform.rotation * centroid.extend(0.0);
+                asteroid_spawn_events.send(AsteroidSpawnEvent {
+                    polygon: fragment.iter().map(|vertex| *vertex - centroid).collect(),
+                    position: transform.translation.truncate() + offset.truncate(),
+                    // Fragments are cut in the asteroid frame, they keep its orientation
+                    rotation: transform.rotation,
+                    // Rigid body velocity at the centroid, plus the push of its side
+                    velocity: Velocity {
+                        linvel: velocity.linvel
+                            + Vec2::new(-offset.y, offset.x) * velocity.angvel
+                            + (transform.rotation * push.extend(0.0)).truncate(),
+                        angvel: velocity.angvel,
+                    },
+                });
+            }
+        }
+    }
+}
+
+fn spawn_asteroid(
+    mut commands: Commands,
+    mut asteroid_spawn_events: EventReader<AsteroidSpawnEvent>,
+) {
+    for spawn_event in asteroid_spawn_events.iter() {
+        let polygon = &spawn_event.polygon;
+        let area = polygon_area(polygon);
+        let indices: Vec<[u32; 2]> = (0..polygon.len() as u32)
+            .map(|index| [index, (index + 1) % polygon.len() as u32])
+            .collect();
+        let shape = shapes::Polygon {
+            points: polygon.clone(),
+            closed: true,
+        };
+        commands.spawn((
+            Asteroid {
+                size: AsteroidSize::of_area(area),
+                polygon: polygon.clone(),
+            },
+            GeometryBuilder::build_as(
+                &shape,
+                DrawMode::Outlined {
+                    fill_mode: FillMode::color(Color::rgb(0.35, 0.25, 0.2)),
+                    outline_mode: StrokeMode::new(Color::rgb(0.6, 0.45, 0.35), 2.0),
+                },
+                Transform::from_translation(spawn_event.position.extend(1.0))
+                    .with_rotation(spawn_event.rotation),
+            ),
+            ForState {
+                states: vec![AppState::Game],
+            },
+            RigidBody::Dynamic,
+            Collider::convex_decomposition(polygon, &indices),
+            ColliderMassProperties::Mass(area * ASTEROID_DENSITY),
+            ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+            ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+            spawn_event.velocity,
+        ));
+    }
+}
+
+/// Random lumpy outline, counter-clockwise around the origin.
+/// Vertices are at growing angles around the origin, so the outline never crosses itself,
+/// but it may be concave.
+pub fn asteroid_polygon(radius: f32) -> Vec<Vec2> {
+    let mut rng = thread_rng();
+    let vertices = rng.gen_range(7..=12);
+    let step = 2.0 * PI / vertices as f32;
+    (0..vertices)
+        .map(|index| {
+            let angle = (index as f32 + rng.gen_range(-0.3..0.3)) * step;
+            Vec2::from_angle(angle) * radius * rng.gen_range(0.55..1.0)
+        })
+        .collect()
+}
+
+pub fn polygon_centroid(polygon: &[Vec2]) -> Vec2 {
+    let area = polygon_area(polygon);
+    if area.abs() < f32::EPSILON {
+        return polygon.iter().sum::<Vec2>() / polygon.len().max(1) as f32;
+    }
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| (*a + *b) * a.perp_dot(*b))
+        .sum::<Vec2>()
+        / (6.0 * area)
+}
+
+/// Connected pieces of a polygon on the side of the line the normal points to,
+/// counter-clockwise like the polygon.
+/// Both sides of a line share the whole area of the polygon, even a concave one.
+fn split_polygon(polygon: &[Vec2], point: Vec2, normal: Vec2) -> Vec<Vec<Vec2>> {
+    let inside = |vertex: Vec2| (vertex - point).dot(normal) >= 0.0;
+    // Outline with its crossings of the line, flagged when leaving the side
+    let mut ring: Vec<(Vec2, Option<bool>)> = Vec::with_capacity(polygon.len() * 2);
+    for (a, b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        ring.push((*a, None));
+        if inside(*a) != inside(*b) {
+            let (side_a, side_b) = ((*a - point).dot(normal), (*b - point).dot(normal));
+            ring.push((
+                *a + (*b - *a) * side_a / (side_a - side_b),
+                Some(inside(*a)),
+            ));
+        }
+    }
+    let mut crossings: Vec<usize> = (0..ring.len()).filter(|i| ring[*i].1.is_some()).collect();
+    if crossings.is_empty() {
+        return if polygon.first().is_some_and(|vertex| inside(*vertex)) {
+            vec![polygon.to_vec()]
+        } else {
+            Vec::new()
+        };
+    }
+    // Crossings sorted along the line bound the chords cut through the polygon, by pairs
+    let along = normal.perp();
+    crossings.sort_by(|a, b| {
+        (ring[*a].0 - point)
+            .dot(along)
+            .total_cmp(&(ring[*b].0 - point).dot(along))
+    });
+    let mut chord_end = vec![0; ring.len()];
+    for pair in crossings.chunks_exact(2) {
+        chord_end[pair[0]] = pair[1];
+        chord_end[pair[1]] = pair[0];
+    }
+
+    // Each piece follows the outline until it leaves the side, then the chord back in
+    let mut pieces = Vec::new();
+    let mut visited = vec![false; ring.len()];
+    for start in crossings.iter().copied() {
+        if ring[start].1 != Some(true) || visited[start] {
+            continue;
+        }
+        let mut piece = Vec::new();
+        let mut exit = start;
+        for _ in 0..ring.len() {
+            visited[exit] = true;
+            piece.push(ring[exit].0);
+            let mut index = chord_end[exit];
+            piece.push(ring[index].0);
+            index = (index + 1) % ring.len();
+            while ring[index].1.is_none() {
+                piece.push(ring[index].0);
+                index = (index + 1) % ring.len();
+            }
+            exit = index;
+            if exit == start || visited[exit] {
+                break;
+            }
+        }
+        piece.dedup_by(|a, b| a.distance_squared(*b) < 0.01);
+        if piece.len() > 1 && piece[0].distance_squared(piece[piece.len() - 1]) < 0.01 {
+            piece.pop();
+        }
+        if piece.len() >= 3 {
+            pieces.push(piece);
+        }
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas(pieces: &[Vec<Vec2>]) -> Vec<f32> {
+        pieces.iter().map(|piece| polygon_area(piece)).collect()
+    }
+
+    #[test]
+    fn fragment_areas_sum_to_the_parent_area() {
+        let mut rng = thread_rng();
+        for _ in 0..500 {
+            let polygon = asteroid_polygon(45.0);
+            let area = polygon_area(&polygon);
+            let point = Vec2::new(rng.gen_range(-40.0..40.0), rng.gen_range(-40.0..40.0));
+            let normal = Vec2::from_angle(rng.gen_range(0.0..2.0 * PI));
+            let pieces: Vec<_> = split_polygon(&polygon, point, normal)
+                .into_iter()
+                .chain(split_polygon(&polygon, point, -normal))
+                .collect();
+            let fragments = areas(&pieces);
+            assert!(fragments.iter().all(|fragment| *fragment > 0.0));
+            let total: f32 = fragments.iter().sum();
+            assert!(
+                (total - area).abs() < area * 1e-3,
+                "fragments {total} of {area}"
+            );
+        }
+    }
+
+    #[test]
+    fn concave_polygon_splits_into_connected_pieces() {
+        // U shape, its arms cut off by a horizontal line
+        let polygon = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 0.0),
+            Vec2::new(30.0, 30.0),
+            Vec2::new(20.0, 30.0),
+            Vec2::new(20.0, 10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(10.0, 30.0),
+            Vec2::new(0.0, 30.0),
+        ];
+        let above = split_polygon(&polygon, Vec2::new(0.0, 20.0), Vec2::Y);
+        let below = split_polygon(&polygon, Vec2::new(0.0, 20.0), -Vec2::Y);
+        assert_eq!(areas(&above), vec![100.0, 100.0]);
+        assert_eq!(areas(&below), vec![500.0]);
+    }
+
+    #[test]
+    fn line_missing_the_polygon_keeps_it_whole() {
+        let polygon = asteroid_polygon(20.0);
+        let point = Vec2::new(0.0, 50.0);
+        assert_eq!(
+            split_polygon(&polygon, point, -Vec2::Y),
+            vec![polygon.clone()]
+        );
+        assert!(split_polygon(&polygon, point, Vec2::Y).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::prelude::*;
+
+/// Angular acceleration rad/s² of the gyros at full turn
+pub const ROTATION_ACCELERATION: f32 = 30.0;
+/// Gains of the heading controller, turning toward the heading and damping the spin.
+/// Chosen for a critically damped response.
+pub const HEADING_GAIN: f32 = 40.0;
+pub const SPIN_DAMPING: f32 = 12.6;
+/// Share of the velocity error px/s corrected per second
+pub const VELOCITY_GAIN: f32 = 2.0;
+/// Velocity error px/s within which the engines stay idle
+pub const VELOCITY_TOLERANCE: f32 = 15.0;
+/// Heading error rad within which the engines burn toward the velocity to match
+pub const BURN_CONE: f32 = 0.5;
+
+/// Label of the system flying the ships, pilots setting autopilot targets run before it
+#[derive(SystemLabel)]
+pub struct FlightControl;
+
+/// Targets the flight controller steers a ship toward.
+/// Pilots, human or not, set them, and manual input overrides them.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Autopilot {
+    /// Whether the controller flies the ship at all
+    pub engaged: bool,
+    /// Direction to face, the ship only stops spinning without one
+    pub heading: Option<Vec2>,
+    /// Velocity px/s to match, the engines stay idle without one
+    pub velocity: Option<Vec2>,
+}
+
+/// Motion and capabilities of a ship, as seen by its flight controller
+#[derive(Clone, Copy, Debug)]
+pub struct FlightState {
+    /// Forward direction of the ship
+    pub heading: Vec2,
+    pub angvel: f32,
+    pub linvel: Vec2,
+    /// Acceleration px/s² of the engines at full throttle
+    pub acceleration: f32,
+    /// Angular acceleration rad/s² of the engines at full throttle,
+    /// as they push off the center of mass, positive turns left
+    pub spin: f32,
+}
+
+/// Flight assist mode of a player ship, applied whenever the pilot leaves the controls alone
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlightAssist {
+    Off,
+    /// Stop spinning
+    KillRotation,
+    /// Keep facing where the ship faced when the pilot stopped turning
+    HoldHeading,
+    /// Burn against the drift until the ship stands still
+    Stop,
+}
+
+impl FlightAssist {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FlightAssist::Off => "Assist off",
+            FlightAssist::KillRotation => "Kill rotation",
+            FlightAssist::HoldHeading => "Hold heading",
+            FlightAssist::Stop => "Stop",
+        }
+    }
+
+    fn next(&self) -> FlightAssist {
+        match self {
+            FlightAssist::Off => FlightAssist::KillRotation,
+            FlightAssist::KillRotation => FlightAssist::HoldHeading,
+            FlightAssist::HoldHeading => FlightAssist::Stop,
+            FlightAssist::Stop => FlightAssist::Off,
+        }
+    }
+}
+
+pub struct AutopilotPlugin;
+
+impl Plugin for AutopilotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(flight_assist_system.before(FlightControl)),
+        );
+    }
+}
+
+/// Throttle 0..1 of the engines toward the velocity target of an autopilot, for any design.
+///
+/// Engines only push forward, so the ship faces the velocity change it needs
+/// and burns once roughly aligned. It burns no harder than the gyros can
+/// counter the spin of off center engines.
+pub fn steer_thrust(autopilot: &Autopilot, state: &FlightState) -> f32 {
+    let Some(error) = velocity_error(autopilot, state) else {
+        return 0.0;
+    };
+    if state.acceleration <= 0.0 {
+        return 0.0;
+    }
+    let alignment = state.heading.angle_between(error).abs();
+    if alignment >= BURN_CONE {
+        return 0.0;
+    }
+    let thrust =
+        (error.length() * VELOCITY_GAIN / state.acceleration * alignment.cos()).clamp(0.0, 1.0);
+    if state.spin != 0.0 {
+        thrust.min(ROTATION_ACCELERATION / state.spin.abs())
+    } else {
+        thrust
+    }
+}
+
+/// PD controller turning the gyros -1..1 toward the targets of an autopilot,
+/// while the engines burn at the given throttle.
+///
+/// The ship faces the velocity change it needs, otherwise its heading.
+/// The spin of the engines is countered ahead of time.
+pub fn steer_turn(autopilot: &Autopilot, state: &FlightState, thrust: f32) -> f32 {
+    let facing = velocity_error(autopilot, state).or(autopilot.heading);
+    let angular_acceleration = match facing {
+        Some(facing) => {
+            HEADING_GAIN * state.heading.angle_between(facing) - SPIN_DAMPING * state.angvel
+        }
+        None => -SPIN_DAMPING * state.angvel,
+    } - thrust * state.spin;
+    (angular_acceleration / ROTATION_ACCELERATION).clamp(-1.0, 1.0)
+}
+
+// Velocity change still needed to match the target, if beyond the tolerance
+fn velocity_error(autopilot: &Autopilot, state: &FlightState) -> Option<Vec2> {
+    autopilot
+        .velocity
+        .map(|velocity| velocity - state.linvel)
+        .filter(|error| error.length() > VELOCITY_TOLERANCE)
+}
+
+// Cycle the assist mode, and set the autopilot targets of the mode.
+// The pilot flies by hand as long as they hold the controls.
+fn flight_assist_system(
+    mut ships: Query<(
+        &ActionState<PlayerAction>,
+        &Transform,
+        &mut FlightAssist,
+        &mut Autopilot,
+    )>,
+) {
+    for (action_state, transform, mut assist, mut autopilot) in ships.iter_mut() {
+        if action_state.just_pressed(PlayerAction::CycleFlightAssist) {
+            *assist = assist.next();
+        }
+        let heading = (transform.rotation * Vec3::Y).truncate();
+        let turning = action_state.pressed(PlayerAction::RotateLeft)
+            || action_state.pressed(PlayerAction::RotateRight);
+        let thrusting = action_state.pressed(PlayerAction::Forward);
+        *autopilot = match *assist {
+            FlightAssist::Off => Autopilot::default(),
+            FlightAssist::KillRotation => Autopilot {
+                engaged: true,
+                ..Default::default()
+            },
+            FlightAssist::HoldHeading => Autopilot {
+                engaged: true,
+                heading: match autopilot.heading {
+                    Some(held) if !turning => Some(held),
+                    _ => Some(heading),
+                },
+                velocity: None,
+            },
+            FlightAssist::Stop => Autopilot {
+                engaged: true,
+                heading: None,
+                velocity: (!thrusting).then_some(Vec2::ZERO),
+            },
+        };
+    }
+}
@@ -0,0 +1,133 @@
+use crate::prelude::*;
+
+/// State of a beam weapon mount
+#[derive(Component, Debug, Default)]
+pub struct BeamEmitter {
+    /// Set by the ship while the trigger is held, cleared once the beam is fired
+    pub firing: bool,
+    /// Entity the beam is held on
+    pub target: Option<Entity>,
+    /// Damage dealt to the target so far, applied by whole points
+    pub damage: f32,
+}
+
+/// Lines of every beam being fired
+#[derive(Component)]
+struct BeamLines;
+
+pub struct BeamPlugin;
+
+impl Plugin for BeamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(AppState::Game).with_system(beam_setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(beam_system.label(ContactResolution)),
+            );
+    }
+}
+
+fn beam_setup(mut commands: Commands) {
+    commands.spawn((
+        BeamLines,
+        GeometryBuilder::build_as(
+            &ShapePath::new().build(),
+            DrawMode::Stroke(StrokeMode::new(BEAM.color, 3.0)),
+            Transform::from_xyz(0.0, 0.0, 2.0),
+        ),
+        ForState {
+            states: vec![AppState::Game],
+        },
+    ));
+}
+
+// Beams are cast from their mount to the first thing they hit, damaging it over time.
+// They heat their mount, and weaken during brownouts.
+fn beam_system(
+    time: Res<Time>,
+    rapier_context: Res<RapierContext>,
+    mut emitters: Query<(
+        &mut BeamEmitter,
+        &ShipWeapon,
+        &mut Heat,
+        &GlobalTransform,
+        &Parent,
+    )>,
+    mut lines: Query<&mut Path, With<BeamLines>>,
+    factions: Factions,
+    grids: Query<&PowerGrid>,
+    asteroids: Query<&Asteroid>,
+    mut parts: Query<(&ShipPart, &mut HitPoints, &Parent)>,
+    mut ships: Query<&mut Ship>,
+    mut shields: Query<&mut Shield>,
+    invulnerable: Query<(), With<Invulnerable>>,
+    mut asteroid_split_events: EventWriter<AsteroidSplitEvent>,
+) {
+    let mut path_builder = PathBuilder::new();
+    for (mut emitter, weapon, mut heat, transform, parent) in emitters.iter_mut() {
+        let Some(definition) = &weapon.kind.definition().beam else {
+            continue;
+        };
+        if !emitter.firing || heat.overheated {
+            emitter.target = None;
+            continue;
+        }
+        emitter.firing = false;
+        let ship = factions.ship_of(parent.get());
+        let power = grids.get(ship).map_or(1.0, PowerGrid::satisfaction);
+        heat.add(weapon.kind.definition().heat * power * time.delta_seconds());
+
+        let origin = transform.translation().truncate();
+        let direction = (transform.compute_transform().rotation * Vec3::Y).truncate();
+        // Beams go through their own ship and its allies, and through sensors
+        let not_friendly_part = |collider: Entity| {
+            parts.get(collider).map_or(true, |(_, _, part_parent)| {
+                factions.hostile(ship, factions.ship_of(part_parent.get()))
+            })
+        };
+        let filter = QueryFilter::new()
+            .exclude_sensors()
+            .predicate(&not_friendly_part);
+        let hit = rapier_context.cast_ray(origin, direction, definition.range, true, filter);
+        let length = hit.map_or(definition.range, |(_, distance)| distance);
+        path_builder.move_to(origin);
+        path_builder.line_to(origin + direction * length);
+
+        let target = hit.map(|(entity, _)| entity);
+        if emitter.target != target {
+            emitter.target = target;
+            emitter.damage = 0.0;
+        }
+        let Some(target) = target else {
+            continue;
+        };
+        emitter.damage += definition.damage_rate * power * time.delta_seconds();
+        if let Ok(asteroid) = asteroids.get(target) {
+            if emitter.damage >= asteroid.size.toughness() as f32 {
+                emitter.damage = 0.0;
+                asteroid_split_events.send(AsteroidSplitEvent {
+                    asteroid: target,
+                    fracture: Some((origin + direction * length, direction)),
+                });
+            }
+        } else if let Ok((part, mut hit_points, part_parent)) = parts.get_mut(target) {
+            let target_ship = factions.ship_of(part_parent.get());
+            if emitter.damage >= 1.0 && !invulnerable.contains(target_ship) {
+                let value = emitter.damage.floor();
+                emitter.damage -= value;
+                let damage = Damage {
+                    value: value as u32,
+                };
+                // Beams go through shield bubbles, but not through the shields themselves
+                let damage = match shields.get_mut(target_ship) {
+                    Ok(mut shield) => shield.absorb(damage),
+                    Err(_) => damage,
+                };
+                damage_part(part, &mut hit_points, target_ship, damage, &mut ships);
+            }
+        }
+    }
+    if let Ok(mut path) = lines.get_single_mut() {
+        *path = path_builder.build();
+    }
+}
@@ -0,0 +1,325 @@
+use std::fmt::Write as _;
+
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, Error, LoadContext, LoadedAsset},
+    reflect::TypeUuid,
+};
+
+use crate::prelude::*;
+
+/// File the design of the player is saved to when leaving build mode, apart from the game assets
+pub const PLAYER_BLUEPRINT_PATH: &str = "saves/player.ship";
+
+/// Design of a ship, restored when the ship is lost.
+///
+/// Saved as text, one part per line in ship local coordinates:
+/// `wall x1 y1 x2 y2`, `engine x y`, `weapon <kind> x y`, `turret <kind> x y`,
+/// `radiator x y`, `collector x y`, `tractor x y`, `shield x y` and `module <kind> x y`.
+/// Empty lines and lines starting with `#` are ignored, and a design holds exactly one cockpit.
+#[derive(Component, Clone, Debug, Default, TypeUuid)]
+#[uuid = "6f1d2b8e-3c4a-4e59-9b7d-2a8c5e0f4b13"]
+pub struct Blueprint {
+    pub parts: Vec<ShipPart>,
+}
+
+impl Blueprint {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for part in self.parts.iter() {
+            let _ = match *part {
+                ShipPart::Wall { start, end } => {
+                    writeln!(text, "wall {} {} {} {}", start.x, start.y, end.x, end.y)
+                }
+                ShipPart::Engine { position } => {
+                    writeln!(text, "engine {} {}", position.x, position.y)
+                }
+                ShipPart::Weapon { position, kind } => writeln!(
+                    text,
+                    "weapon {} {} {}",
+                    kind.definition().name,
+                    position.x,
+                    position.y
+                ),
+                ShipPart::Turret { position, kind } => writeln!(
+                    text,
+                    "turret {} {} {}",
+                    kind.definition().name,
+                    position.x,
+                    position.y
+                ),
+                ShipPart::Radiator { position } => {
+                    writeln!(text, "radiator {} {}", position.x, position.y)
+                }
+                ShipPart::Collector { position } => {
+                    writeln!(text, "collector {} {}", position.x, position.y)
+                }
+                ShipPart::Tractor { position } => {
+                    writeln!(text, "tractor {} {}", position.x, position.y)
+                }
+                ShipPart::Shield { position } => {
+                    writeln!(text, "shield {} {}", position.x, position.y)
+                }
+                ShipPart::Module { kind, position } => {
+                    writeln!(text, "module {} {} {}", kind.name(), position.x, position.y)
+                }
+            };
+        }
+        text
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let part = parse_part(line).map_err(|error| format!("line {}: {error}", index + 1))?;
+            parts.push(part);
+        }
+        let cockpits = parts
+            .iter()
+            .filter(|part| {
+                matches!(
+                    part,
+                    ShipPart::Module {
+                        kind: ModuleKind::Cockpit,
+                        ..
+                    }
+                )
+            })
+            .count();
+        if cockpits != 1 {
+            return Err(format!("expected one cockpit, found {cockpits}"));
+        }
+        Ok(Blueprint { parts })
+    }
+}
+
+fn parse_part(line: &str) -> Result<ShipPart, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    // Coordinates following the part name, and its kind if any
+    let coordinates = |skip: usize, count: usize| -> Result<Vec<f32>, String> {
+        let values = words
+            .iter()
+            .skip(skip)
+            .map(|word| {
+                word.parse::<f32>()
+                    .map_err(|_| format!("invalid number {word}"))
+            })
+            .collect::<Result<Vec<f32>, String>>()?;
+        if values.len() != count {
+            return Err(format!("expected {count} coordinates"));
+        }
+        Ok(values)
+    };
+    let position = |skip: usize| coordinates(skip, 2).map(|values| Vec2::new(values[0], values[1]));
+    let kind = words.get(1).copied().unwrap_or_default();
+    let weapon = || {
+        WeaponKind::ALL
+            .into_iter()
+            .find(|weapon| weapon.definition().name == kind)
+            .ok_or_else(|| format!("unknown weapon {kind}"))
+    };
+    match words.first().copied().unwrap_or_default() {
+        "wall" => {
+            let values = coordinates(1, 4)?;
+            Ok(ShipPart::Wall {
+                start: Vec2::new(values[0], values[1]),
+                end: Vec2::new(values[2], values[3]),
+            })
+        }
+        "engine" => Ok(ShipPart::Engine {
+            position: position(1)?,
+        }),
+        "weapon" => Ok(ShipPart::Weapon {
+            kind: weapon()?,
+            position: position(2)?,
+        }),
+        "turret" => Ok(ShipPart::Turret {
+            kind: weapon()?,
+            position: position(2)?,
+        }),
+        "radiator" => Ok(ShipPart::Radiator {
+            position: position(1)?,
+        }),
+        "collector" => Ok(ShipPart::Collector {
+            position: position(1)?,
+        }),
+        "tractor" => Ok(ShipPart::Tractor {
+            position: position(1)?,
+        }),
+        "shield" => Ok(ShipPart::Shield {
+            position: position(1)?,
+        }),
+        "module" => Ok(ShipPart::Module {
+            kind: ModuleKind::ALL
+                .into_iter()
+                .find(|module| module.name() == kind)
+                .ok_or_else(|| format!("unknown module {kind}"))?,
+            position: position(2)?,
+        }),
+        other => Err(format!("unknown part {other}")),
+    }
+}
+
+pub struct BlueprintPlugin;
+
+impl Plugin for BlueprintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Blueprint>()
+            .init_asset_loader::<BlueprintLoader>();
+    }
+}
+
+/// Loads the `.ship` files as blueprint assets
+#[derive(Default)]
+struct BlueprintLoader;
+
+impl AssetLoader for BlueprintLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let blueprint =
+                Blueprint::from_text(std::str::from_utf8(bytes)?).map_err(Error::msg)?;
+            load_context.set_default_asset(LoadedAsset::new(blueprint));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ship"]
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_blueprint(path: &str, blueprint: &Blueprint) {
+    let path = std::path::Path::new(path);
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(path, blueprint.to_text()));
+    if let Err(error) = result {
+        warn!("Could not save blueprint {}: {error}", path.display());
+    }
+}
+
+// Browsers have no file system to save to
+#[cfg(target_arch = "wasm32")]
+pub fn save_blueprint(_path: &str, _blueprint: &Blueprint) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> String {
+        Blueprint::from_text(text).unwrap_err()
+    }
+
+    #[test]
+    fn blueprint_round_trips_through_text() {
+        let blueprint = Blueprint {
+            parts: vec![
+                ShipPart::Wall {
+                    start: Vec2::new(-40.0, -20.0),
+                    end: Vec2::new(40.5, 20.0),
+                },
+                ShipPart::Engine {
+                    position: Vec2::new(0.0, -20.0),
+                },
+                ShipPart::Weapon {
+                    position: Vec2::new(0.0, 20.0),
+                    kind: WeaponKind::Laser,
+                },
+                ShipPart::Turret {
+                    position: Vec2::new(20.0, 20.0),
+                    kind: WeaponKind::ALL[WeaponKind::ALL.len() - 1],
+                },
+                ShipPart::Radiator {
+                    position: Vec2::new(-40.0, 0.0),
+                },
+                ShipPart::Collector {
+                    position: Vec2::new(-20.0, 20.0),
+                },
+                ShipPart::Tractor {
+                    position: Vec2::new(40.0, 0.0),
+                },
+                ShipPart::Shield {
+                    position: Vec2::new(-20.0, -20.0),
+                },
+                ShipPart::Module {
+                    kind: ModuleKind::Cockpit,
+                    position: Vec2::ZERO,
+                },
+            ],
+        };
+        let parsed = Blueprint::from_text(&blueprint.to_text()).unwrap();
+        assert_eq!(parsed.parts, blueprint.parts);
+    }
+
+    #[test]
+    fn shipped_designs_parse() {
+        for text in [
+            include_str!("../assets/blueprints/gunship.ship"),
+            include_str!("../assets/blueprints/scout.ship"),
+            include_str!("../assets/blueprints/striker.ship"),
+        ] {
+            assert!(Blueprint::from_text(text).is_ok());
+        }
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_ignored() {
+        let blueprint = Blueprint::from_text("# design\n\n  module cockpit 0 0  \n").unwrap();
+        assert_eq!(blueprint.parts.len(), 1);
+    }
+
+    #[test]
+    fn unknown_parts_and_kinds_are_rejected() {
+        assert_eq!(
+            error("module cockpit 0 0\nhull 0 0"),
+            "line 2: unknown part hull"
+        );
+        assert_eq!(
+            error("module cockpit 0 0\nweapon cannon 0 20"),
+            "line 2: unknown weapon cannon"
+        );
+        assert_eq!(error("module bridge 0 0"), "line 1: unknown module bridge");
+    }
+
+    #[test]
+    fn malformed_coordinates_are_rejected() {
+        assert_eq!(
+            error("module cockpit 0 0\nengine 0 x"),
+            "line 2: invalid number x"
+        );
+        assert_eq!(
+            error("module cockpit 0 0\nengine 0"),
+            "line 2: expected 2 coordinates"
+        );
+        assert_eq!(
+            error("module cockpit 0 0\nwall 0 0 20"),
+            "line 2: expected 4 coordinates"
+        );
+        assert_eq!(
+            error("module cockpit 0 0\nradiator 0 0 0"),
+            "line 2: expected 2 coordinates"
+        );
+    }
+
+    #[test]
+    fn designs_need_exactly_one_cockpit() {
+        assert_eq!(error(""), "expected one cockpit, found 0");
+        assert_eq!(
+            error("engine 0 -20\nmodule reactor 0 0"),
+            "expected one cockpit, found 0"
+        );
+        assert_eq!(
+            error("module cockpit 0 0\nmodule cockpit 20 0"),
+            "expected one cockpit, found 2"
+        );
+    }
+}
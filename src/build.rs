@@ -1,5 +1,8 @@
 use crate::prelude::*;
 
+/// Size px of the grid parts are snapped to
+pub const GRID_SIZE: f32 = 20.0;
+
 pub struct BuildPlugin;
 
 #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug)]
@@ -7,13 +10,36 @@ pub enum BuildAction {
     DeselectTool,
     SelectWallTool,
     SelectEngineTool,
+    SelectModuleTool,
+    SelectWeaponTool,
+    SelectRadiatorTool,
+    SelectCollectorTool,
+    SelectTractorTool,
+    SelectShieldTool,
+    SelectTurretTool,
+    NextModule,
+    NextWeapon,
 }
 
 #[derive(Component, Debug, Default)]
 struct WallTool {}
 
 #[derive(Component, Debug, Default)]
-struct EngineTool {}
+struct ModuleTool {}
+
+/// Module placed by the module tool
+#[derive(Debug, Resource)]
+pub struct ModuleSelection {
+    pub kind: ModuleKind,
+    /// Used and total area px2, and perimeter px of the room under the cursor
+    pub room_usage: Option<(f32, f32, f32)>,
+}
+
+/// Weapon mounted by the weapon tool
+#[derive(Debug, Resource)]
+pub struct WeaponSelection {
+    pub kind: WeaponKind,
+}
 
 impl Plugin for BuildPlugin {
     fn build(&self, app: &mut App) {
@@ -25,8 +51,42 @@ impl Plugin for BuildPlugin {
         app.add_system_set(
             SystemSet::on_update(BuildState::WallTool).with_system(wall_tool_system),
         );
+        app.add_system_set(SystemSet::on_update(BuildState::EngineTool).with_system(
+            point_tool_system(|position, _| ShipPart::Engine { position }),
+        ));
+        app.add_system_set(
+            SystemSet::on_update(BuildState::ModuleTool).with_system(module_tool_system),
+        );
+        app.add_system_set(
+            SystemSet::on_update(BuildState::WeaponTool)
+                .with_system(weapon_select_system)
+                .with_system(point_tool_system(|position, kind| ShipPart::Weapon {
+                    position,
+                    kind,
+                })),
+        );
+        app.add_system_set(SystemSet::on_update(BuildState::RadiatorTool).with_system(
+            point_tool_system(|position, _| ShipPart::Radiator { position }),
+        ));
+        app.add_system_set(SystemSet::on_update(BuildState::CollectorTool).with_system(
+            point_tool_system(|position, _| ShipPart::Collector { position }),
+        ));
+        app.add_system_set(SystemSet::on_update(BuildState::TractorTool).with_system(
+            point_tool_system(|position, _| ShipPart::Tractor { position }),
+        ));
+        app.add_system_set(SystemSet::on_update(BuildState::ShieldTool).with_system(
+            point_tool_system(|position, _| ShipPart::Shield { position }),
+        ));
         app.add_system_set(
-            SystemSet::on_update(BuildState::EngineTool).with_system(engine_tool_system),
+            SystemSet::on_update(BuildState::TurretTool)
+                .with_system(weapon_select_system)
+                .with_system(point_tool_system(|position, kind| ShipPart::Turret {
+                    position,
+                    kind,
+                })),
+        );
+        app.add_system_set(
+            SystemSet::on_exit(BuildState::ModuleTool).with_system(module_tool_exit),
         );
     }
 }
@@ -36,9 +96,25 @@ fn setup(mut commands: Commands) {
         (KeyCode::Key0, BuildAction::DeselectTool),
         (KeyCode::Key1, BuildAction::SelectWallTool),
         (KeyCode::Key2, BuildAction::SelectEngineTool),
+        (KeyCode::Key3, BuildAction::SelectModuleTool),
+        (KeyCode::Key4, BuildAction::SelectWeaponTool),
+        (KeyCode::Key5, BuildAction::SelectRadiatorTool),
+        (KeyCode::Key6, BuildAction::SelectCollectorTool),
+        (KeyCode::Key7, BuildAction::SelectTractorTool),
+        (KeyCode::Key8, BuildAction::SelectShieldTool),
+        (KeyCode::Key9, BuildAction::SelectTurretTool),
+        (KeyCode::E, BuildAction::NextModule),
+        (KeyCode::Tab, BuildAction::NextWeapon),
     ]);
     commands.insert_resource(input_map);
     commands.insert_resource(ActionState::<BuildAction>::default());
+    commands.insert_resource(ModuleSelection {
+        kind: ModuleKind::Cockpit,
+        room_usage: None,
+    });
+    commands.insert_resource(WeaponSelection {
+        kind: WeaponKind::Laser,
+    });
     commands.spawn((
         WallTool::default(),
         GeometryBuilder::build_as(
@@ -47,6 +123,20 @@ fn setup(mut commands: Commands) {
             Transform::default(),
         ),
     ));
+    commands.spawn((
+        ModuleTool::default(),
+        ForState {
+            states: vec![AppState::Game],
+        },
+        GeometryBuilder::build_as(
+            &shapes::Rectangle {
+                extents: Vec2::splat(MODULE_SIZE),
+                origin: RectangleOrigin::Center,
+            },
+            DrawMode::Stroke(StrokeMode::new(Color::GREEN, 2.0)),
+            Transform::default(),
+        ),
+    ));
 }
 
 fn tool_select_system(
@@ -68,37 +158,77 @@ fn tool_select_system(
     {
         buildstate.set(BuildState::EngineTool).unwrap();
     }
+    if buildstate.current() != &BuildState::ModuleTool
+        && action_state.just_pressed(BuildAction::SelectModuleTool)
+    {
+        buildstate.set(BuildState::ModuleTool).unwrap();
+    }
+    if buildstate.current() != &BuildState::WeaponTool
+        && action_state.just_pressed(BuildAction::SelectWeaponTool)
+    {
+        buildstate.set(BuildState::WeaponTool).unwrap();
+    }
+    if buildstate.current() != &BuildState::RadiatorTool
+        && action_state.just_pressed(BuildAction::SelectRadiatorTool)
+    {
+        buildstate.set(BuildState::RadiatorTool).unwrap();
+    }
+    if buildstate.current() != &BuildState::CollectorTool
+        && action_state.just_pressed(BuildAction::SelectCollectorTool)
+    {
+        buildstate.set(BuildState::CollectorTool).unwrap();
+    }
+    if buildstate.current() != &BuildState::TractorTool
+        && action_state.just_pressed(BuildAction::SelectTractorTool)
+    {
+        buildstate.set(BuildState::TractorTool).unwrap();
+    }
+    if buildstate.current() != &BuildState::ShieldTool
+        && action_state.just_pressed(BuildAction::SelectShieldTool)
+    {
+        buildstate.set(BuildState::ShieldTool).unwrap();
+    }
+    if buildstate.current() != &BuildState::TurretTool
+        && action_state.just_pressed(BuildAction::SelectTurretTool)
+    {
+        buildstate.set(BuildState::TurretTool).unwrap();
+    }
 }
 
 fn wall_tool_system(
     windows: Res<Windows>,
     camera: Query<(&Camera, &GlobalTransform)>,
-    ship_transforms: Query<&GlobalTransform, With<Ship>>,
+    mut commands: Commands,
+    ships: Query<(Entity, &GlobalTransform), (With<Ship>, Without<Enemy>)>,
     mut wall_tools: Query<&mut Path, With<WallTool>>,
-    mut wall_paths: Query<&mut Path, (With<ShipWalls>, Without<WallTool>)>,
     buttons: Res<Input<MouseButton>>,
+    mut ore: ResMut<Ore>,
 ) {
     if let Some(cursor_global) = get_cursor_position(windows, camera) {
-        let ship_transform = ship_transforms.single();
+        let Ok((ship_entity, ship_transform)) = ships.get_single() else {
+            return;
+        };
         let cursor_local = point_relative_to_transform(cursor_global, ship_transform).truncate();
-        let cursor = round_to_grid(cursor_local, 20.0);
+        let cursor = round_to_grid(cursor_local, GRID_SIZE);
         if buttons.just_pressed(MouseButton::Left) {
-            let wall_tool_path = wall_tools.single();
-            let new_wall_tool_path = if wall_tool_path.0.first_endpoint().is_none() {
-                let mut path_builder = PathBuilder::new();
-                path_builder.move_to(cursor);
-                path_builder.build()
-            } else {
-                let new_wall_path = ShapePath::new()
-                    .add(wall_paths.single())
-                    .add(wall_tool_path)
-                    .build();
-                let mut wall_path = wall_paths.single_mut();
-                *wall_path = new_wall_path;
-                ShapePath::new().build()
-            };
             let mut wall_tool_path = wall_tools.single_mut();
-            *wall_tool_path = new_wall_tool_path;
+            *wall_tool_path = match wall_tool_path.0.first_endpoint() {
+                None => {
+                    let mut path_builder = PathBuilder::new();
+                    path_builder.move_to(cursor);
+                    path_builder.build()
+                }
+                Some(point) => {
+                    let start = Vec2::new(point.0.x, point.0.y);
+                    let wall = ShipPart::Wall { start, end: cursor };
+                    if start != cursor && ore.spend(wall.cost()) {
+                        commands.entity(ship_entity).add_children(|parent| {
+                            wall.spawn(parent);
+                        });
+                    }
+                    ShapePath::new().build()
+                }
+            };
         }
 
         if let Ok(mut wall_tool_path) = wall_tools.get_single_mut() {
@@ -115,40 +245,143 @@ fn wall_tool_system(
     }
 }
 
-fn engine_tool_system(
+/// System of a tool placing a single part at the cursor.
+/// The part is built from the cursor position, and the selected weapon for the weapon mounts.
+fn point_tool_system(
+    part: fn(Vec2, WeaponKind) -> ShipPart,
+) -> impl FnMut(
+    Res<Windows>,
+    Query<(&Camera, &GlobalTransform)>,
+    Commands,
+    Query<(Entity, &GlobalTransform), (With<Ship>, Without<Enemy>)>,
+    Res<Input<MouseButton>>,
+    ResMut<Ore>,
+    Res<WeaponSelection>,
+) {
+    move |windows, camera, mut commands, ships, buttons, mut ore, selection| {
+        if let Some(cursor_global) = get_cursor_position(windows, camera) {
+            let Ok((ship_entity, ship_transform)) = ships.get_single() else {
+                return;
+            };
+            let cursor_local =
+                point_relative_to_transform(cursor_global, ship_transform).truncate();
+            let part = part(round_to_grid(cursor_local, GRID_SIZE), selection.kind);
+            if buttons.just_pressed(MouseButton::Left) && ore.spend(part.cost()) {
+                commands.entity(ship_entity).add_children(|parent| {
+                    part.spawn(parent);
+                });
+            }
+        }
+    }
+}
+
+fn weapon_select_system(
+    action_state: Res<ActionState<BuildAction>>,
+    mut selection: ResMut<WeaponSelection>,
+) {
+    if action_state.just_pressed(BuildAction::NextWeapon) {
+        let index = WeaponKind::ALL
+            .iter()
+            .position(|kind| *kind == selection.kind)
+            .unwrap_or_default();
+        selection.kind = WeaponKind::ALL[(index + 1) % WeaponKind::ALL.len()];
+    }
+}
+
+fn module_tool_system(
     windows: Res<Windows>,
     camera: Query<(&Camera, &GlobalTransform)>,
     mut commands: Commands,
-    ships: Query<(Entity, &GlobalTransform), With<Ship>>,
+    action_state: Res<ActionState<BuildAction>>,
+    mut selection: ResMut<ModuleSelection>,
+    ships: Query<(Entity, &GlobalTransform, &Children), (With<Ship>, Without<Enemy>)>,
+    parts: Query<&ShipPart>,
+    rooms: Query<&Room>,
+    mut module_tools: Query<(&mut Transform, &mut DrawMode, &mut Visibility), With<ModuleTool>>,
     buttons: Res<Input<MouseButton>>,
+    mut ore: ResMut<Ore>,
 ) {
-    if let Some(cursor_global) = get_cursor_position(windows, camera) {
-        let (ship_entity, ship_transform) = ships.single();
-        let cursor_local = point_relative_to_transform(cursor_global, ship_transform).truncate();
-        let cursor = round_to_grid(cursor_local, 20.0);
-        if buttons.just_pressed(MouseButton::Left) {
-            let engines = RegularPolygon {
-                sides: 4,
-                feature: shapes::RegularPolygonFeature::Radius(5.0),
-                ..shapes::RegularPolygon::default()
-            };
+    if action_state.just_pressed(BuildAction::NextModule) {
+        let index = ModuleKind::ALL
+            .iter()
+            .position(|kind| *kind == selection.kind)
+            .unwrap_or_default();
+        selection.kind = ModuleKind::ALL[(index + 1) % ModuleKind::ALL.len()];
+    }
+    let Ok((mut tool_transform, mut tool_draw_mode, mut tool_visibility)) =
+        module_tools.get_single_mut()
+    else {
+        return;
+    };
+    tool_visibility.is_visible = false;
+    selection.room_usage = None;
+    let Some(cursor_global) = get_cursor_position(windows, camera) else {
+        return;
+    };
+    let Ok((ship_entity, ship_transform, children)) = ships.get_single() else {
+        return;
+    };
+    let cursor_local = point_relative_to_transform(cursor_global, ship_transform).truncate();
+    let cursor = round_to_grid(cursor_local, GRID_SIZE);
+    let ship_parts: Vec<ShipPart> = parts.iter_many(children).copied().collect();
+    let room = rooms.iter_many(children).find(|room| room.contains(cursor));
+    let valid = room.is_some_and(|room| {
+        let used_area = room.used_area(&ship_parts);
+        selection.room_usage = Some((used_area, room.area, room.perimeter));
+        module_fits(selection.kind, cursor, room, used_area, &ship_parts)
+    }) && ore.amount >= selection.kind.cost();
 
-            commands
-                .get_entity(ship_entity)
-                .unwrap()
-                .add_children(|parent| {
-                    parent.spawn((
-                        ShipEngine {},
-                        GeometryBuilder::build_as(
-                            &ShapePath::build_as(&engines),
-                            DrawMode::Fill(FillMode::color(Color::RED)),
-                            Transform {
-                                translation: Vec3::new(cursor.x, cursor.y, 0.0),
-                                ..Default::default()
-                            },
-                        ),
-                    ));
-                });
-        }
+    // Preview the module, red when it can not be placed there or afforded
+    *tool_transform = ship_transform
+        .mul_transform(Transform::from_translation(cursor.extend(0.2)))
+        .compute_transform();
+    *tool_draw_mode = DrawMode::Stroke(StrokeMode::new(
+        if valid { Color::GREEN } else { Color::RED },
+        2.0,
+    ));
+    tool_visibility.is_visible = true;
+
+    if valid && buttons.just_pressed(MouseButton::Left) && ore.spend(selection.kind.cost()) {
+        commands.entity(ship_entity).add_children(|parent| {
+            ShipPart::Module {
+                kind: selection.kind,
+                position: cursor,
+            }
+            .spawn(parent);
+        });
+    }
+}
+
+fn module_tool_exit(
+    mut selection: ResMut<ModuleSelection>,
+    mut module_tools: Query<&mut Visibility, With<ModuleTool>>,
+) {
+    selection.room_usage = None;
+    for mut visibility in module_tools.iter_mut() {
+        visibility.is_visible = false;
     }
 }
+
+/// A module must fit inside its room, clear of the walls and the other modules,
+/// and the room must have enough area left for it.
+/// A ship only has a single cockpit.
+fn module_fits(
+    kind: ModuleKind,
+    position: Vec2,
+    room: &Room,
+    used_area: f32,
+    parts: &[ShipPart],
+) -> bool {
+    room.distance_to_walls(position) >= (MODULE_SIZE + WALL_WIDTH) / 2.0
+        && used_area + kind.footprint() <= room.area
+        && parts.iter().all(|part| match *part {
+            ShipPart::Module {
+                kind: other_kind,
+                position: other_position,
+            } => {
+                (position - other_position).abs().max_element() >= MODULE_SIZE
+                    && !(kind == ModuleKind::Cockpit && other_kind == ModuleKind::Cockpit)
+            }
+            _ => true,
+        })
+}
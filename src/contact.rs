@@ -0,0 +1,230 @@
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+/// Contact force N below which colliders do not report impacts
+pub const IMPACT_FORCE_THRESHOLD: f32 = 1000.0;
+/// Impact impulse N.s absorbed without damage, slow bumps are harmless
+pub const IMPACT_IMPULSE_THRESHOLD: f32 = 30.0;
+/// Impact impulse N.s beyond the threshold dealing one damage point
+pub const IMPACT_IMPULSE_PER_DAMAGE: f32 = 25.0;
+
+/// Label of the system resolving collisions, systems reacting to its outcome run after it
+#[derive(SystemLabel)]
+pub struct ContactResolution;
+
+pub struct ContactPlugin;
+
+impl Plugin for ContactPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(contact_system.label(ContactResolution))
+                .with_system(impact_system.label(ContactResolution)),
+        );
+    }
+}
+
+// Dispatch every collision between projectiles, asteroids and ship parts.
+// Projectiles are spent on their first hit and asteroids are split by them.
+// Missile blasts damage every part and asteroid they reach, except those of the shooter and its allies.
+// Ore pickups are collected by the collectors they touch.
+// Shots go through the ships of their own side.
+// Shield bubbles drain the projectiles of hostile ships while they hold, stopping those they absorb,
+// and shields soak up the damage dealt to the parts of their ship.
+// Invulnerable ships are not damaged.
+fn contact_system(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut projectile_despawn_events: EventWriter<ProjectileDespawnEvent>,
+    mut asteroid_split_events: EventWriter<AsteroidSplitEvent>,
+    mut explosion_spawn_events: EventWriter<ExplosionSpawnEvent>,
+    mut ore_collect_events: EventWriter<OreCollectEvent>,
+    mut projectiles: Query<(&Projectile, &mut Damage, &Transform)>,
+    asteroids: Query<(), With<Asteroid>>,
+    blasts: Query<&Blast>,
+    pickups: Query<(), With<OrePickup>>,
+    collectors: Query<(), With<ShipCollector>>,
+    bubbles: Query<&ShieldBubble>,
+    mut parts: Query<(&ShipPart, &mut HitPoints, &Parent)>,
+    factions: Factions,
+    mut ships: Query<&mut Ship>,
+    mut shields: Query<&mut Shield>,
+    invulnerable: Query<(), With<Invulnerable>>,
+) {
+    let mut spent_projectiles = HashSet::default();
+    for event in collision_events.iter() {
+        let CollisionEvent::Started(e1, e2, _) = event else {
+            continue;
+        };
+        for (source, target) in [(*e1, *e2), (*e2, *e1)] {
+            if spent_projectiles.contains(&source) {
+                continue;
+            }
+            if pickups.contains(source) {
+                if collectors.contains(target) {
+                    ore_collect_events.send(OreCollectEvent(source));
+                }
+                continue;
+            }
+            // Ship owning the part hit, if any
+            let target_ship = parts
+                .get(target)
+                .ok()
+                .map(|(_, _, parent)| factions.ship_of(parent.get()));
+            let damage = if let Ok(blast) = blasts.get(source) {
+                if asteroids.contains(target) {
+                    asteroid_split_events.send(AsteroidSplitEvent {
+                        asteroid: target,
+                        fracture: None,
+                    });
+                    continue;
+                }
+                if target_ship.is_none_or(|ship| !factions.hostile(blast.shooter, ship)) {
+                    continue;
+                }
+                Damage {
+                    value: blast.damage,
+                }
+            } else if let Ok((projectile, mut damage, transform)) = projectiles.get_mut(source) {
+                let hit = ExplosionSpawnEvent {
+                    kind: ExplosionKind::ProjectileHit,
+                    position: transform.translation.truncate(),
+                };
+                if let Ok(bubble) = bubbles.get(target) {
+                    // Shots leave through the bubbles of their own side
+                    if !factions.hostile(projectile.shooter, bubble.ship) {
+                        continue;
+                    }
+                    let Ok(mut shield) = shields.get_mut(bubble.ship) else {
+                        continue;
+                    };
+                    if shield.strength <= 0.0 {
+                        continue;
+                    }
+                    explosion_spawn_events.send(hit);
+                    if !invulnerable.contains(bubble.ship) {
+                        // Shots the shield can not fully absorb go on with the remaining damage
+                        let remaining = shield.absorb(*damage);
+                        if remaining.value > 0 {
+                            *damage = remaining;
+                            continue;
+                        }
+                    }
+                    spent_projectiles.insert(source);
+                    projectile_despawn_events.send(ProjectileDespawnEvent(source));
+                    continue;
+                }
+                if asteroids.contains(target) {
+                    spent_projectiles.insert(source);
+                    projectile_despawn_events.send(ProjectileDespawnEvent(source));
+                    // Asteroids crack along the projectile path
+                    asteroid_split_events.send(AsteroidSplitEvent {
+                        asteroid: target,
+                        fracture: Some((
+                            transform.translation.truncate(),
+                            (transform.rotation * Vec3::Y).truncate(),
+                        )),
+                    });
+                    explosion_spawn_events.send(hit);
+                    continue;
+                }
+                // A ship can not shoot itself, any of its sections, nor its allies
+                match target_ship {
+                    Some(ship) if factions.hostile(projectile.shooter, ship) => {}
+                    _ => continue,
+                }
+                spent_projectiles.insert(source);
+                projectile_despawn_events.send(ProjectileDespawnEvent(source));
+                explosion_spawn_events.send(hit);
+                *damage
+            } else {
+                continue;
+            };
+            let Some(target_ship) = target_ship else {
+                continue;
+            };
+            if invulnerable.contains(target_ship) {
+                continue;
+            }
+            let damage = match shields.get_mut(target_ship) {
+                Ok(mut shield) => shield.absorb(damage),
+                Err(_) => damage,
+            };
+
+            if let Ok((part, mut hit_points, _)) = parts.get_mut(target) {
+                damage_part(part, &mut hit_points, target_ship, damage, &mut ships);
+            }
+        }
+    }
+}
+
+// Turn the impulse of hard impacts into damage on the colliding parts, so that ramming hurts.
+// Asteroids rammed hard enough break as if shot, shields soak up the damage to parts.
+fn impact_system(
+    rapier_context: Res<RapierContext>,
+    mut contact_force_events: EventReader<ContactForceEvent>,
+    mut asteroid_split_events: EventWriter<AsteroidSplitEvent>,
+    asteroids: Query<&Asteroid>,
+    mut parts: Query<(&ShipPart, &mut HitPoints, &Parent)>,
+    sections: Query<&ShipSection>,
+    mut ships: Query<&mut Ship>,
+    mut shields: Query<&mut Shield>,
+    invulnerable: Query<(), With<Invulnerable>>,
+) {
+    let dt = rapier_context.integration_parameters.dt;
+    for event in contact_force_events.iter() {
+        let impulse = event.total_force_magnitude * dt;
+        if impulse <= IMPACT_IMPULSE_THRESHOLD {
+            continue;
+        }
+        let damage = Damage {
+            value: ((impulse - IMPACT_IMPULSE_THRESHOLD) / IMPACT_IMPULSE_PER_DAMAGE).ceil() as u32,
+        };
+        for target in [event.collider1, event.collider2] {
+            if let Ok(asteroid) = asteroids.get(target) {
+                if damage.value >= asteroid.size.toughness() {
+                    asteroid_split_events.send(AsteroidSplitEvent {
+                        asteroid: target,
+                        fracture: None,
+                    });
+                }
+            } else if let Ok((part, mut hit_points, parent)) = parts.get_mut(target) {
+                let ship = sections
+                    .get(parent.get())
+                    .map_or(parent.get(), |section| section.ship);
+                if !invulnerable.contains(ship) {
+                    let damage = match shields.get_mut(ship) {
+                        Ok(mut shield) => shield.absorb(damage),
+                        Err(_) => damage,
+                    };
+                    damage_part(part, &mut hit_points, ship, damage, &mut ships);
+                }
+            }
+        }
+    }
+}
+
+/// Apply damage to a ship part, its ship loses a life with its cockpit.
+/// The ship is the root body, even for parts of its sections.
+pub fn damage_part(
+    part: &ShipPart,
+    hit_points: &mut HitPoints,
+    ship: Entity,
+    damage: Damage,
+    ships: &mut Query<&mut Ship>,
+) {
+    let intact = hit_points.current > 0;
+    hit_points.apply(damage);
+    let is_cockpit = matches!(
+        part,
+        ShipPart::Module {
+            kind: ModuleKind::Cockpit,
+            ..
+        }
+    );
+    if intact && hit_points.current == 0 && is_cockpit {
+        if let Ok(mut ship) = ships.get_mut(ship) {
+            ship.life = ship.life.saturating_sub(1);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::prelude::*;
+
+/// Damage dealt by an entity to the ship parts it collides with.
+/// Applied by the contact resolution.
+#[derive(Component, Clone, Copy)]
+pub struct Damage {
+    pub value: u32,
+}
+
+/// Hit points of a single ship part.
+/// The part is removed from the ship design when they reach 0.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct HitPoints {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl HitPoints {
+    pub fn new(max: u32) -> Self {
+        HitPoints { current: max, max }
+    }
+
+    pub fn apply(&mut self, damage: Damage) {
+        self.current = self.current.saturating_sub(damage.value);
+    }
+}
+
+/// Sent when a part is destroyed, with the rigid body it was part of
+pub struct PartDestroyedEvent {
+    pub body: Entity,
+}
+
+pub struct DamagePlugin;
+
+impl Plugin for DamagePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PartDestroyedEvent>().add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(part_damage_color_system.after(ContactResolution))
+                .with_system(part_destruction_system.after(ContactResolution)),
+        );
+    }
+}
+
+// Fade damaged parts out, a part about to be destroyed is barely visible
+fn part_damage_color_system(mut parts: Query<(&HitPoints, &mut DrawMode), Changed<HitPoints>>) {
+    for (hit_points, mut draw_mode) in parts.iter_mut() {
+        let alpha = 0.25 + 0.75 * hit_points.current as f32 / hit_points.max as f32;
+        match draw_mode.as_mut() {
+            DrawMode::Fill(fill_mode) => {
+                fill_mode.color.set_a(alpha);
+            }
+            DrawMode::Stroke(stroke_mode) => {
+                stroke_mode.color.set_a(alpha);
+            }
+            DrawMode::Outlined {
+                fill_mode,
+                outline_mode,
+            } => {
+                fill_mode.color.set_a(alpha);
+                outline_mode.color.set_a(alpha);
+            }
+        }
+    }
+}
+
+// Remove destroyed parts from their ship.
+// Rapier recomputes the ship collider and mass from the remaining parts.
+fn part_destruction_system(
+    mut commands: Commands,
+    mut part_destroyed_events: EventWriter<PartDestroyedEvent>,
+    parts: Query<(Entity, &HitPoints, &Parent), Changed<HitPoints>>,
+) {
+    for (entity, hit_points, parent) in parts.iter() {
+        if hit_points.current == 0 {
+            commands.entity(entity).despawn_recursive();
+            part_destroyed_events.send(PartDestroyedEvent { body: parent.get() });
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+/// Rigid body made of ship parts that got disconnected from their ship core
+#[derive(Component)]
+pub struct Debris;
+
+pub struct DebrisPlugin;
+
+impl Plugin for DebrisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(ship_fragmentation_system)
+                .with_system(debris_cleanup_system),
+        );
+    }
+}
+
+// Split off every group of parts no longer attached to the ship core, into its own debris body
+fn ship_fragmentation_system(
+    mut commands: Commands,
+    mut part_destroyed_events: EventReader<PartDestroyedEvent>,
+    rapier_context: Res<RapierContext>,
+    ships: Query<(&Transform, &Velocity, &RapierRigidBodyHandle), With<Ship>>,
+    bodies: Query<(Entity, &Children, Option<&ShipSection>)>,
+    parts: Query<(Entity, &ShipPart, PartState)>,
+) {
+    let damaged_ships: HashSet<Entity> = part_destroyed_events
+        .iter()
+        .map(|event| match bodies.get(event.body) {
+            Ok((_, _, Some(section))) => section.ship,
+            _ => event.body,
+        })
+        .collect();
+    for ship_entity in damaged_ships {
+        if let Ok((transform, velocity, body_handle)) = ships.get(ship_entity) {
+            // Parts of every section of the ship, all sharing the ship local coordinates.
+            // Parts destroyed this frame may not be despawned yet.
+            let remaining: Vec<_> = bodies
+                .iter()
+                .filter(|(body, _, section)| {
+                    *body == ship_entity
+                        || section.is_some_and(|section| section.ship == ship_entity)
+                })
+                .flat_map(|(_, children, _)| parts.iter_many(children))
+                .filter(|(_, _, state)| state.hit_points.current > 0)
+                .collect();
+            let designs: Vec<ShipPart> = remaining.iter().map(|(_, part, _)| **part).collect();
+            let core = core_part(&designs);
+            for group in connected_parts(&designs) {
+                if core.is_some_and(|core| group.contains(&core)) {
+                    continue;
+                }
+                let centroid = group
+                    .iter()
+                    .map(|index| designs[*index].center())
+                    .sum::<Vec2>()
+                    / group.len() as f32;
+                let split_point = transform.transform_point(centroid.extend(0.0)).truncate();
+                let linvel = body_velocity_at_point(&rapier_context, body_handle.0, split_point)
+                    .unwrap_or(velocity.linvel);
+                commands
+                    .spawn((
+                        Debris,
+                        ForState {
+                            states: vec![AppState::Game],
+                        },
+                        RigidBody::Dynamic,
+                        Velocity {
+                            linvel,
+                            angvel: velocity.angvel,
+                        },
+                        GeometryBuilder::build_as(
+                            &PathBuilder::new().build(),
+                            DrawMode::Stroke(StrokeMode::new(Color::WHITE, WALL_WIDTH)),
+                            *transform,
+                        ),
+                    ))
+                    .with_children(|parent| {
+                        for index in group.iter() {
+                            let (_, part, state) = &remaining[*index];
+                            part.respawn(parent, state);
+                        }
+                    });
+                for index in group {
+                    commands.entity(remaining[index].0).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+// Despawn debris once all their parts are destroyed
+fn debris_cleanup_system(
+    mut commands: Commands,
+    debris: Query<(Entity, Option<&Children>), With<Debris>>,
+    parts: Query<&ShipPart>,
+) {
+    for (entity, children) in debris.iter() {
+        if children.is_none_or(|children| parts.iter_many(children).next().is_none()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
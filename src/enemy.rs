@@ -0,0 +1,226 @@
+use bevy::ecs::system::SystemParam;
+use rand::seq::SliceRandom;
+
+use crate::prelude::*;
+
+/// Score for destroying an enemy ship
+pub const ENEMY_SCORE: u32 = 100;
+/// Closest and farthest distance px pilots hold from their target
+pub const ENEMY_MIN_RANGE: f32 = 120.0;
+pub const ENEMY_MAX_RANGE: f32 = 350.0;
+/// Speed px/s pilots close in, back off and dodge at
+pub const ENEMY_CRUISE_SPEED: f32 = 150.0;
+/// Heading error rad within which pilots fire at their target
+pub const ENEMY_FIRE_CONE: f32 = 0.15;
+/// Time s ahead pilots look for asteroids on a collision course
+pub const ENEMY_AVOID_HORIZON: f32 = 2.0;
+/// Gap px pilots keep from the asteroids they dodge
+pub const ENEMY_AVOID_CLEARANCE: f32 = 40.0;
+
+/// Ship flown by an AI pilot, through the same actions as a player
+#[derive(Component, Debug)]
+pub struct Enemy {
+    /// Distance px the pilot keeps from its target
+    pub range: f32,
+    /// Distance px its weapons reach
+    pub reach: f32,
+}
+
+/// Owners and sides of the bodies in the arena.
+/// Enemies fight the player ships but not each other, asteroids belong to no one.
+#[derive(SystemParam)]
+pub struct Factions<'w, 's> {
+    sections: Query<'w, 's, &'static ShipSection>,
+    enemies: Query<'w, 's, (), With<Enemy>>,
+}
+
+impl<'w, 's> Factions<'w, 's> {
+    /// Ship owning a body, sections belong to their ship root
+    pub fn ship_of(&self, body: Entity) -> Entity {
+        self.sections.get(body).map_or(body, |section| section.ship)
+    }
+
+    /// Whether two ships fight each other, a ship never fights itself
+    pub fn hostile(&self, ship: Entity, other: Entity) -> bool {
+        ship != other && self.enemies.contains(ship) != self.enemies.contains(other)
+    }
+}
+
+/// Designs enemy ships are built after
+pub const ENEMY_BLUEPRINTS: [&str; 3] = [
+    "blueprints/gunship.ship",
+    "blueprints/scout.ship",
+    "blueprints/striker.ship",
+];
+
+#[derive(Debug, Default, Resource)]
+pub struct EnemyBlueprints(pub Vec<Handle<Blueprint>>);
+
+/// Sent to bring an enemy ship into the arena
+#[derive(Clone, Copy)]
+pub struct EnemySpawnEvent {
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<EnemySpawnEvent>()
+            .add_system_set(SystemSet::on_enter(AppState::Game).with_system(enemy_setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(spawn_enemy)
+                    .with_system(enemy_pilot_system.before(FlightControl)),
+            );
+    }
+}
+
+fn enemy_setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(EnemyBlueprints(
+        ENEMY_BLUEPRINTS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect(),
+    ));
+}
+
+// Enemies are built after a random loaded design, heading where they are thrown.
+// They wait for the first design to load.
+fn spawn_enemy(
+    mut commands: Commands,
+    mut enemy_spawn_events: EventReader<EnemySpawnEvent>,
+    mut pending: Local<Vec<EnemySpawnEvent>>,
+    blueprints: Res<EnemyBlueprints>,
+    blueprint_assets: Res<Assets<Blueprint>>,
+) {
+    pending.extend(enemy_spawn_events.iter().copied());
+    let loaded: Vec<&Blueprint> = blueprints
+        .0
+        .iter()
+        .filter_map(|handle| blueprint_assets.get(handle))
+        .collect();
+    if loaded.is_empty() {
+        return;
+    }
+    let mut rng = thread_rng();
+    for spawn_event in pending.drain(..) {
+        let Some(blueprint) = loaded.choose(&mut rng) else {
+            continue;
+        };
+        let reach = blueprint
+            .parts
+            .iter()
+            .filter_map(|part| match *part {
+                ShipPart::Weapon { kind, .. } | ShipPart::Turret { kind, .. } => {
+                    Some(kind.definition().reach())
+                }
+                _ => None,
+            })
+            .fold(0.0, f32::max);
+        let transform = Transform::from_translation(spawn_event.position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(
+                (-spawn_event.velocity.x).atan2(spawn_event.velocity.y),
+            ));
+        commands
+            .spawn((
+                ship_bundle(0, 0, transform, Velocity::linear(spawn_event.velocity)),
+                Enemy {
+                    range: (reach * 0.6).clamp(ENEMY_MIN_RANGE, ENEMY_MAX_RANGE),
+                    reach,
+                },
+                TurretMode::Auto,
+                ActionState::<PlayerAction>::default(),
+            ))
+            .with_children(|parent| {
+                for part in blueprint.parts.iter() {
+                    part.spawn(parent);
+                }
+            })
+            .insert((*blueprint).clone());
+    }
+}
+
+// Pilots chase the nearest player ship and hold their range, dodging the asteroids
+// on a collision course, and fire once their guns bear on the target.
+// They fly through their autopilot, facing the target whenever their speed allows.
+fn enemy_pilot_system(
+    mut enemies: Query<(
+        &Enemy,
+        &Transform,
+        &Velocity,
+        &mut Autopilot,
+        &mut ActionState<PlayerAction>,
+    )>,
+    targets: Query<(&Transform, &Velocity), (With<Ship>, Without<Enemy>)>,
+    asteroids: Query<(&Transform, &Velocity, &Asteroid)>,
+) {
+    for (enemy, transform, velocity, mut autopilot, mut action_state) in enemies.iter_mut() {
+        let position = transform.translation.truncate();
+        let heading = (transform.rotation * Vec3::Y).truncate();
+        let Some((offset, target_velocity)) = targets
+            .iter()
+            .map(|(target, target_velocity)| {
+                (
+                    target.translation.truncate() - position,
+                    target_velocity.linvel,
+                )
+            })
+            .min_by(|a, b| a.0.length().total_cmp(&b.0.length()))
+        else {
+            // Idle pilots stop spinning and drift
+            *autopilot = Autopilot {
+                engaged: true,
+                ..Default::default()
+            };
+            hold(&mut action_state, PlayerAction::Fire, false);
+            continue;
+        };
+        let distance = offset.length();
+        let toward = offset.normalize_or_zero();
+
+        // Where the soonest asteroid on a collision course passes closest
+        let threat = asteroids
+            .iter()
+            .filter_map(|(asteroid_transform, asteroid_velocity, asteroid)| {
+                let relative_position = asteroid_transform.translation.truncate() - position;
+                let relative_velocity = asteroid_velocity.linvel - velocity.linvel;
+                let time = -relative_position.dot(relative_velocity)
+                    / relative_velocity.length_squared().max(f32::EPSILON);
+                let closest = relative_position + relative_velocity * time;
+                ((0.0..ENEMY_AVOID_HORIZON).contains(&time)
+                    && closest.length() < asteroid.size.radius() + ENEMY_AVOID_CLEARANCE)
+                    .then_some((time, closest))
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+
+        let desired_velocity = if let Some((_, closest)) = threat {
+            -closest.try_normalize().unwrap_or(heading.perp()) * ENEMY_CRUISE_SPEED
+        } else if distance > enemy.range * 1.2 {
+            target_velocity + toward * ENEMY_CRUISE_SPEED
+        } else if distance < enemy.range * 0.5 {
+            target_velocity - toward * ENEMY_CRUISE_SPEED
+        } else {
+            target_velocity
+        };
+        *autopilot = Autopilot {
+            engaged: true,
+            heading: offset.try_normalize(),
+            velocity: Some(desired_velocity),
+        };
+        hold(
+            &mut action_state,
+            PlayerAction::Fire,
+            distance <= enemy.reach && heading.angle_between(offset).abs() < ENEMY_FIRE_CONE,
+        );
+    }
+}
+
+fn hold(action_state: &mut ActionState<PlayerAction>, action: PlayerAction, pressed: bool) {
+    if pressed && !action_state.pressed(action) {
+        action_state.press(action);
+    } else if !pressed && action_state.pressed(action) {
+        action_state.release(action);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExplosionKind {
+    /// A ship losing its cockpit
+    ShipDeath,
+    /// A projectile hitting an asteroid or a ship part
+    ProjectileHit,
+    /// A missile blowing up
+    MissileBlast,
+}
+
+impl ExplosionKind {
+    /// Duration s of the animation
+    fn duration(&self) -> f32 {
+        match self {
+            ExplosionKind::ShipDeath => 0.8,
+            ExplosionKind::ProjectileHit => 0.3,
+            ExplosionKind::MissileBlast => 0.5,
+        }
+    }
+
+    /// Sprite scale at the start and end of the animation
+    fn scales(&self) -> (f32, f32) {
+        match self {
+            ExplosionKind::ShipDeath => (0.1, 0.5),
+            ExplosionKind::ProjectileHit => (0.02, 0.08),
+            ExplosionKind::MissileBlast => (0.05, 0.3),
+        }
+    }
+}
+
+pub struct ExplosionSpawnEvent {
+    pub kind: ExplosionKind,
+    pub position: Vec2,
+}
+
+#[derive(Component)]
+pub struct Explosion {
+    pub kind: ExplosionKind,
+    pub timer: Timer,
+}
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ExplosionSpawnEvent>().add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(spawn_explosion)
+                .with_system(animate_explosion_system),
+        );
+    }
+}
+
+fn spawn_explosion(
+    mut commands: Commands,
+    mut explosion_spawn_events: EventReader<ExplosionSpawnEvent>,
+    handles: Res<SpriteAssets>,
+    audios: Res<AudioAssets>,
+    audio_output: Res<Audio>,
+) {
+    for spawn_event in explosion_spawn_events.iter() {
+        let (texture, sound) = match spawn_event.kind {
+            ExplosionKind::ShipDeath | ExplosionKind::MissileBlast => (
+                handles.ship_explosion.clone(),
+                audios.ship_explosion.clone(),
+            ),
+            ExplosionKind::ProjectileHit => {
+                (handles.ship_contact.clone(), audios.ship_contact.clone())
+            }
+        };
+        let (start_scale, _) = spawn_event.kind.scales();
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform {
+                    translation: spawn_event.position.extend(3.0),
+                    scale: Vec3::splat(start_scale),
+                    ..Default::default()
+                },
+                texture,
+                ..Default::default()
+            },
+            Explosion {
+                kind: spawn_event.kind,
+                timer: Timer::from_seconds(spawn_event.kind.duration(), TimerMode::Once),
+            },
+            ForState {
+                states: vec![AppState::Game],
+            },
+        ));
+        audio_output.play(sound);
+    }
+}
+
+// Explosions grow and fade out, then are removed
+fn animate_explosion_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut Sprite, &mut Explosion)>,
+) {
+    for (entity, mut transform, mut sprite, mut explosion) in query.iter_mut() {
+        explosion.timer.tick(time.delta());
+        if explosion.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            let progress = explosion.timer.percent();
+            let (start_scale, end_scale) = explosion.kind.scales();
+            transform.scale = Vec3::splat(start_scale + (end_scale - start_scale) * progress);
+            sprite.color.set_a(1.0 - progress);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+/// Fuel units a tank holds when full
+pub const FUEL_TANK_CAPACITY: f32 = 100.0;
+/// Mass of an empty tank
+pub const FUEL_TANK_MASS: f32 = 1.0;
+/// Mass of a single fuel unit
+pub const FUEL_MASS: f32 = 0.02;
+/// Fuel units burnt per second by an engine at full thrust
+pub const ENGINE_FUEL_BURN: f32 = 2.0;
+
+/// Fuel held by a single tank part
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FuelTank {
+    pub fuel: f32,
+    pub capacity: f32,
+}
+
+impl FuelTank {
+    pub fn full() -> Self {
+        FuelTank {
+            fuel: FUEL_TANK_CAPACITY,
+            capacity: FUEL_TANK_CAPACITY,
+        }
+    }
+}
+
+/// Fuel left in all the tanks of a ship
+#[derive(Component, Debug, Default)]
+pub struct FuelGauge {
+    pub fuel: f32,
+    pub capacity: f32,
+    /// Fuel units burnt per second by the engines at full thrust
+    pub engines: f32,
+    /// Fuel units burnt per second by the engines in use
+    pub burn: f32,
+}
+
+pub struct FuelPlugin;
+
+impl Plugin for FuelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(fuel_gauge_system)
+                .with_system(fuel_burn_system)
+                .with_system(fuel_tank_mass_system.after(fuel_burn_system)),
+        );
+    }
+}
+
+// Sum the fuel left in the tanks of every ship, including its sections
+fn fuel_gauge_system(
+    mut ships: Query<(Entity, &mut FuelGauge)>,
+    sections: Query<&ShipSection>,
+    tanks: Query<(&FuelTank, &Parent)>,
+    engines: Query<(&ShipEngine, &Heat, &Parent)>,
+) {
+    let ship_of = |parent: &Parent| {
+        sections
+            .get(parent.get())
+            .map_or(parent.get(), |section| section.ship)
+    };
+    for (_, mut gauge) in ships.iter_mut() {
+        gauge.fuel = 0.0;
+        gauge.capacity = 0.0;
+        gauge.engines = 0.0;
+    }
+    for (tank, parent) in tanks.iter() {
+        if let Ok((_, mut gauge)) = ships.get_mut(ship_of(parent)) {
+            gauge.fuel += tank.fuel;
+            gauge.capacity += tank.capacity;
+        }
+    }
+    for (engine, _, parent) in engines.iter().filter(|(_, heat, _)| !heat.overheated) {
+        if let Ok((_, mut gauge)) = ships.get_mut(ship_of(parent)) {
+            gauge.engines += engine.fuel;
+        }
+    }
+}
+
+// Drain the tanks of every ship by the fuel its engines burnt, one tank after the other
+fn fuel_burn_system(
+    time: Res<Time>,
+    mut ships: Query<(Entity, &mut FuelGauge)>,
+    sections: Query<&ShipSection>,
+    mut tanks: Query<(&mut FuelTank, &Parent)>,
+) {
+    for (ship_entity, mut gauge) in ships.iter_mut() {
+        let mut burn = gauge.burn * time.delta_seconds();
+        // Engines only burn fuel on the frames they are in use
+        gauge.burn = 0.0;
+        for (mut tank, parent) in tanks.iter_mut() {
+            if burn <= 0.0 {
+                break;
+            }
+            let tank_ship = sections
+                .get(parent.get())
+                .map_or(parent.get(), |section| section.ship);
+            if tank_ship != ship_entity || tank.fuel <= 0.0 {
+                continue;
+            }
+            let drained = tank.fuel.min(burn);
+            tank.fuel -= drained;
+            burn -= drained;
+        }
+    }
+}
+
+// Ships get lighter as they burn fuel
+fn fuel_tank_mass_system(
+    mut tanks: Query<(&FuelTank, &mut ColliderMassProperties), Changed<FuelTank>>,
+) {
+    for (tank, mut mass_properties) in tanks.iter_mut() {
+        *mass_properties = ColliderMassProperties::Mass(FUEL_TANK_MASS + tank.fuel * FUEL_MASS);
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::utils::HashMap;
+
+use crate::prelude::*;
+
+/// Heat at which a part shuts down
+pub const OVERHEAT: f32 = 100.0;
+/// Heat an overheated part must cool down to, before working again
+pub const RESTART_HEAT: f32 = 50.0;
+/// Heat generated per second by an engine at full thrust
+pub const ENGINE_HEAT: f32 = 15.0;
+/// Heat dissipated per second by a radiator
+pub const RADIATOR_COOLING: f32 = 40.0;
+/// Heat lost per second by every part
+pub const PASSIVE_COOLING: f32 = 1.0;
+/// Share of the heat difference flowing per second between two parts attached by a wall
+pub const HEAT_CONDUCTION: f32 = 2.0;
+
+/// Heat of a single ship part
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Heat {
+    pub value: f32,
+    /// Overheated parts stop working until they cool down
+    pub overheated: bool,
+}
+
+impl Heat {
+    pub fn add(&mut self, heat: f32) {
+        self.value = (self.value + heat).max(0.0);
+        if self.value >= OVERHEAT {
+            self.overheated = true;
+        } else if self.value <= RESTART_HEAT {
+            self.overheated = false;
+        }
+    }
+}
+
+pub struct HeatPlugin;
+
+impl Plugin for HeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(engine_heat_system)
+                .with_system(heat_conduction_system.after(engine_heat_system))
+                .with_system(heat_cooling_system.after(heat_conduction_system))
+                .with_system(heat_color_system.after(heat_cooling_system)),
+        );
+    }
+}
+
+// Engines heat up with the thrust of their ship
+fn engine_heat_system(
+    time: Res<Time>,
+    ships: Query<&Ship>,
+    sections: Query<&ShipSection>,
+    mut engines: Query<(&mut Heat, &Parent), With<ShipEngine>>,
+) {
+    for (mut heat, parent) in engines.iter_mut() {
+        let ship_entity = sections
+            .get(parent.get())
+            .map_or(parent.get(), |section| section.ship);
+        if let Ok(ship) = ships.get(ship_entity) {
+            if ship.throttle > 0.0 && !heat.overheated {
+                heat.add(ENGINE_HEAT * ship.throttle * time.delta_seconds());
+            }
+        }
+    }
+}
+
+// Heat spreads between the parts of a ship through its walls
+fn heat_conduction_system(
+    time: Res<Time>,
+    bodies: Query<(Entity, &Children, Option<&ShipSection>)>,
+    mut parts: Query<(&ShipPart, &mut Heat)>,
+) {
+    // Sections share their ship local coordinates, heat flows through the joints too
+    let mut ship_parts: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    for (entity, children, section) in bodies.iter() {
+        let ship_entity = section.map_or(entity, |section| section.ship);
+        ship_parts
+            .entry(ship_entity)
+            .or_default()
+            .extend(children.iter().filter(|child| parts.contains(**child)));
+    }
+    let rate = (HEAT_CONDUCTION * time.delta_seconds()).min(0.5);
+    for entities in ship_parts.values() {
+        let states: Vec<(ShipPart, f32)> = parts
+            .iter_many(entities)
+            .map(|(part, heat)| (*part, heat.value))
+            .collect();
+        let mut flows = vec![0.0; states.len()];
+        for (index, (part, heat)) in states.iter().enumerate() {
+            for (other_index, (other, other_heat)) in states.iter().enumerate().skip(index + 1) {
+                let through_wall =
+                    matches!(part, ShipPart::Wall { .. }) || matches!(other, ShipPart::Wall { .. });
+                if through_wall && part.is_attached_to(other) {
+                    let flow = (heat - other_heat) * rate / 2.0;
+                    flows[index] -= flow;
+                    flows[other_index] += flow;
+                }
+            }
+        }
+        for (index, entity) in entities.iter().enumerate() {
+            if flows[index] != 0.0 {
+                if let Ok((_, mut heat)) = parts.get_mut(*entity) {
+                    heat.add(flows[index]);
+                }
+            }
+        }
+    }
+}
+
+fn heat_cooling_system(time: Res<Time>, mut parts: Query<(&mut Heat, Option<&ShipRadiator>)>) {
+    for (mut heat, radiator) in parts.iter_mut() {
+        if heat.value > 0.0 {
+            let cooling = PASSIVE_COOLING + radiator.map_or(0.0, |radiator| radiator.cooling);
+            heat.add(-cooling * time.delta_seconds());
+        }
+    }
+}
+
+// Hot parts glow red
+fn heat_color_system(mut parts: Query<(&ShipPart, &Heat, &mut DrawMode), Changed<Heat>>) {
+    for (part, heat, mut draw_mode) in parts.iter_mut() {
+        let glow = (heat.value / OVERHEAT).clamp(0.0, 1.0);
+        let color = part.color() * (1.0 - glow) + Color::RED * glow;
+        let shift = |target: &mut Color| {
+            let alpha = target.a();
+            *target = color;
+            target.set_a(alpha);
+        };
+        match draw_mode.as_mut() {
+            DrawMode::Fill(fill_mode) => shift(&mut fill_mode.color),
+            DrawMode::Stroke(stroke_mode) => shift(&mut stroke_mode.color),
+            DrawMode::Outlined { fill_mode, .. } => shift(&mut fill_mode.color),
+        }
+    }
+}
@@ -2,21 +2,58 @@ use crate::prelude::*;
 
 #[derive(Component)]
 pub struct UiScore {}
+/// Ore available for building
+#[derive(Component)]
+pub struct UiOre {}
 #[derive(Component)]
 pub struct UiLife {
     pub min: u32,
 }
 
+/// Share of the reactors output in use, red during brownouts
+#[derive(Component)]
+pub struct UiPowerBar {}
+
+/// Fuel left in the tanks
+#[derive(Component)]
+pub struct UiFuelBar {}
+
+/// Flight assist mode of the player ship
+#[derive(Component)]
+pub struct UiFlightAssist {}
+
+/// Centered banner announcing the waves and counting down the breathers
+#[derive(Component)]
+pub struct UiWaveBanner {}
+
+/// Build HUD text showing the selected module and the room under the cursor
+#[derive(Component)]
+pub struct UiModuleSelection {}
+
+/// Build HUD text showing the weapon mounted by the weapon tool
+#[derive(Component)]
+pub struct UiWeaponSelection {}
+
 pub struct HudPlugin;
 impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_set(
             SystemSet::on_update(AppState::Game)
                 .with_system(hud_score_system)
-                .with_system(hud_life_system),
+                .with_system(hud_ore_system)
+                .with_system(hud_life_system)
+                .with_system(hud_power_system)
+                .with_system(hud_fuel_system)
+                .with_system(hud_flight_assist_system)
+                .with_system(hud_wave_system),
         )
         .add_system_set(SystemSet::on_enter(AppState::Game).with_system(hud_spawn))
         .add_system_set(SystemSet::on_enter(PlayerState::Building).with_system(build_hud_spawn))
+        .add_system_set(
+            SystemSet::on_update(PlayerState::Building)
+                .with_system(build_hud_module_system)
+                .with_system(build_hud_weapon_system),
+        )
         .add_system_set(SystemSet::on_exit(PlayerState::Building).with_system(build_hud_despawn));
     }
 }
@@ -40,6 +77,54 @@ fn hud_spawn(mut commands: Commands, assets: ResMut<UiAssets>) {
             },
         ))
         .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        justify_content: JustifyContent::FlexEnd,
+                        margin: UiRect {
+                            left: Val::Px(10.0),
+                            right: Val::Px(10.0),
+                            top: Val::Px(20.0),
+                            bottom: Val::Px(10.0),
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    ..Default::default()
+                },
+                UiFlightAssist {},
+            ));
+            parent.spawn((
+                TextBundle {
+                    style: Style {
+                        justify_content: JustifyContent::FlexEnd,
+                        margin: UiRect {
+                            left: Val::Px(10.0),
+                            right: Val::Px(10.0),
+                            top: Val::Px(10.0),
+                            bottom: Val::Px(10.0),
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::from_section(
+                        "0",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 50.0,
+                            color: Color::GOLD,
+                        },
+                    ),
+                    ..Default::default()
+                },
+                UiOre {},
+            ));
             parent.spawn((
                 TextBundle {
                     style: Style {
@@ -104,6 +189,87 @@ fn hud_spawn(mut commands: Commands, assets: ResMut<UiAssets>) {
                 ));
             }
         });
+    // Power and fuel bars
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    align_items: AlignItems::FlexStart,
+                    justify_content: JustifyContent::FlexStart,
+                    flex_direction: FlexDirection::ColumnReverse,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ForState {
+                states: vec![AppState::Game],
+            },
+        ))
+        .with_children(|parent| {
+            hud_bar_spawn(parent, Color::YELLOW, UiPowerBar {});
+            hud_bar_spawn(parent, Color::GREEN, UiFuelBar {});
+        });
+    // Wave banner
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ForState {
+                states: vec![AppState::Game],
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: assets.font.clone(),
+                            font_size: 50.0,
+                            color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                        },
+                    ),
+                    ..Default::default()
+                },
+                UiWaveBanner {},
+            ));
+        });
+}
+
+fn hud_bar_spawn(parent: &mut ChildBuilder, color: Color, marker: impl Component) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(200.0), Val::Px(10.0)),
+                margin: UiRect::all(Val::Px(10.0)),
+                ..Default::default()
+            },
+            background_color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                        ..Default::default()
+                    },
+                    background_color: color.into(),
+                    ..Default::default()
+                },
+                marker,
+            ));
+        });
 }
 
 fn hud_score_system(arena: Res<Arena>, mut query: Query<&mut Text, With<UiScore>>) {
@@ -113,6 +279,13 @@ fn hud_score_system(arena: Res<Arena>, mut query: Query<&mut Text, With<UiScore>
         }
     }
 }
+fn hud_ore_system(ore: Res<Ore>, mut query: Query<&mut Text, With<UiOre>>) {
+    if ore.is_changed() {
+        for mut text in query.iter_mut() {
+            text.sections[0].value = format!("{}", ore.amount);
+        }
+    }
+}
 fn hud_life_system(ship_query: Query<&Ship>, mut uilife_query: Query<(&mut Visibility, &UiLife)>) {
     let mut life = 0;
     for ship in ship_query.iter() {
@@ -125,6 +298,82 @@ fn hud_life_system(ship_query: Query<&Ship>, mut uilife_query: Query<(&mut Visib
     }
 }
 
+fn hud_power_system(
+    ship_query: Query<(&Ship, &PowerGrid)>,
+    mut uipower_query: Query<(&mut Style, &mut BackgroundColor), With<UiPowerBar>>,
+) {
+    let mut load = 0.0;
+    for (ship, grid) in ship_query.iter() {
+        if ship.player_id == 1 && grid.demand > 0.0 {
+            load = grid.demand / grid.supply;
+        }
+    }
+    for (mut style, mut color) in uipower_query.iter_mut() {
+        style.size.width = Val::Percent(100.0 * load.min(1.0));
+        *color = if load > 1.0 {
+            Color::RED.into()
+        } else {
+            Color::YELLOW.into()
+        };
+    }
+}
+
+fn hud_fuel_system(
+    ship_query: Query<(&Ship, &FuelGauge)>,
+    mut uifuel_query: Query<&mut Style, With<UiFuelBar>>,
+) {
+    let mut level = 0.0;
+    for (ship, gauge) in ship_query.iter() {
+        if ship.player_id == 1 && gauge.capacity > 0.0 {
+            level = gauge.fuel / gauge.capacity;
+        }
+    }
+    for mut style in uifuel_query.iter_mut() {
+        style.size.width = Val::Percent(100.0 * level);
+    }
+}
+
+fn hud_flight_assist_system(
+    ships: Query<&FlightAssist, Changed<FlightAssist>>,
+    mut query: Query<&mut Text, With<UiFlightAssist>>,
+) {
+    for assist in ships.iter() {
+        for mut text in query.iter_mut() {
+            text.sections[0].value = format!("Q {}", assist.name());
+        }
+    }
+}
+
+// Announce each wave as it starts, and count down to the next one during breathers
+fn hud_wave_system(
+    director: Res<WaveDirector>,
+    mut query: Query<(&mut Text, &mut Visibility), With<UiWaveBanner>>,
+) {
+    if !director.is_changed() {
+        return;
+    }
+    let banner = match director.countdown() {
+        Some(remaining) if director.wave == 0 => {
+            Some(format!("First wave in {}", remaining.ceil()))
+        }
+        Some(remaining) => Some(format!(
+            "Wave {} cleared\nF to build - next wave in {}",
+            director.wave,
+            remaining.ceil()
+        )),
+        None if director.elapsed - director.wave_start < WAVE_BANNER_DURATION => {
+            Some(format!("Wave {}", director.wave))
+        }
+        None => None,
+    };
+    for (mut text, mut visibility) in query.iter_mut() {
+        visibility.is_visible = banner.is_some();
+        if let Some(banner) = &banner {
+            text.sections[0].value = banner.clone();
+        }
+    }
+}
+
 fn build_hud_spawn(mut commands: Commands, assets: ResMut<UiAssets>) {
     commands
         .spawn((
@@ -202,10 +451,234 @@ fn build_hud_spawn(mut commands: Commands, assets: ResMut<UiAssets>) {
                         ),
                         ..Default::default()
                     });
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "3",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                justify_content: JustifyContent::FlexEnd,
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    right: Val::Px(10.0),
+                                    top: Val::Px(10.0),
+                                    bottom: Val::Px(10.0),
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: 25.0,
+                                    color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        UiModuleSelection {},
+                    ));
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "4",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                justify_content: JustifyContent::FlexEnd,
+                                margin: UiRect {
+                                    left: Val::Px(10.0),
+                                    right: Val::Px(10.0),
+                                    top: Val::Px(10.0),
+                                    bottom: Val::Px(10.0),
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    font: assets.font.clone(),
+                                    font_size: 25.0,
+                                    color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                                },
+                            ),
+                            ..Default::default()
+                        },
+                        UiWeaponSelection {},
+                    ));
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "5",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "6",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "7",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "8",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
+                    parent.spawn(TextBundle {
+                        style: Style {
+                            justify_content: JustifyContent::FlexEnd,
+                            margin: UiRect {
+                                left: Val::Px(10.0),
+                                right: Val::Px(10.0),
+                                top: Val::Px(10.0),
+                                bottom: Val::Px(10.0),
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::from_section(
+                            "9",
+                            TextStyle {
+                                font: assets.font.clone(),
+                                font_size: 50.0,
+                                color: Color::rgb_u8(0x00, 0xAA, 0xAA),
+                            },
+                        ),
+                        ..Default::default()
+                    });
                 });
         });
 }
 
+fn build_hud_module_system(
+    selection: Res<ModuleSelection>,
+    mut query: Query<&mut Text, With<UiModuleSelection>>,
+) {
+    for mut text in query.iter_mut() {
+        text.sections[0].value = match selection.room_usage {
+            Some((used_area, area, perimeter)) => format!(
+                "{} {:.0}/{:.0} walls {:.0}",
+                selection.kind.name(),
+                used_area,
+                area,
+                perimeter
+            ),
+            None => selection.kind.name().to_string(),
+        };
+    }
+}
+
+fn build_hud_weapon_system(
+    selection: Res<WeaponSelection>,
+    mut query: Query<&mut Text, With<UiWeaponSelection>>,
+) {
+    if selection.is_changed() {
+        for mut text in query.iter_mut() {
+            text.sections[0].value = selection.kind.definition().name.to_string();
+        }
+    }
+}
+
 fn build_hud_despawn(mut commands: Commands, query: Query<(Entity, &ForState<PlayerState>)>) {
     for (entity, for_state) in &mut query.iter() {
         if for_state.states.contains(&PlayerState::Building) {
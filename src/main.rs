@@ -1,28 +1,74 @@
 #![allow(clippy::too_many_arguments)]
+#![allow(clippy::type_complexity)]
 mod arena;
 mod assets;
+mod asteroid;
+mod autopilot;
 mod background;
+mod beam;
+mod blueprint;
 mod build;
+mod contact;
+mod damage;
+mod debris;
+mod enemy;
+mod explosion;
+mod fuel;
+mod heat;
 mod hud;
-mod laser;
 mod menu;
+mod missile;
+mod ore;
 mod particle_effects;
+mod parts;
 mod player_ship;
+mod power;
+mod projectile;
+mod rooms;
+mod shield;
 mod state;
+mod structure;
+mod tractor;
+mod turret;
 mod util;
+mod wave;
+mod weapon;
 
 mod prelude {
     pub use crate::arena::*;
     pub use crate::assets::*;
+    pub use crate::asteroid::*;
+    pub use crate::autopilot::*;
     pub use crate::background::*;
+    pub use crate::beam::*;
+    pub use crate::blueprint::*;
     pub use crate::build::*;
+    pub use crate::contact::*;
+    pub use crate::damage::*;
+    pub use crate::debris::*;
+    pub use crate::enemy::*;
+    pub use crate::explosion::*;
+    pub use crate::fuel::*;
+    pub use crate::heat::*;
     pub use crate::hud::*;
-    pub use crate::laser::*;
     pub use crate::menu::*;
+    pub use crate::missile::*;
+    pub use crate::ore::*;
+    pub use crate::parts::*;
     pub use crate::player_ship::*;
+    pub use crate::power::*;
+    pub use crate::projectile::*;
+    pub use crate::rooms::*;
+    pub use crate::shield::*;
     pub use crate::state::*;
+    pub use crate::structure::*;
+    pub use crate::tractor::*;
+    pub use crate::turret::*;
     pub use crate::util::*;
+    pub use crate::wave::*;
+    pub use crate::weapon::*;
     pub use bevy::prelude::*;
+    pub use bevy_prototype_lyon::prelude::FillMode;
     pub use bevy_prototype_lyon::prelude::*;
     pub use bevy_rapier2d::prelude::*;
     pub use leafwing_input_manager::prelude::*;
@@ -56,18 +102,41 @@ fn main() {
     #[cfg(debug_assertions)]
     app.add_plugin(RapierDebugRenderPlugin::default());
 
-    app.add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(10.0));
+    app.add_plugin(RapierPhysicsPlugin::<&ShipSection>::pixels_per_meter(10.0))
+        .insert_resource(PhysicsHooksWithQueryResource::<&ShipSection>(Box::new(
+            SectionContactFilter,
+        )));
     app.add_plugin(InputManagerPlugin::<MenuAction>::default());
 
     app.add_plugin(AssetsPlugin)
         .add_plugin(ArenaPlugin)
+        .add_plugin(AsteroidPlugin)
+        .add_plugin(AutopilotPlugin)
         .add_plugin(BackgroundPlugin)
+        .add_plugin(BeamPlugin)
+        .add_plugin(BlueprintPlugin)
         .add_plugin(BuildPlugin)
+        .add_plugin(ContactPlugin)
+        .add_plugin(DamagePlugin)
+        .add_plugin(DebrisPlugin)
+        .add_plugin(EnemyPlugin)
+        .add_plugin(ExplosionPlugin)
+        .add_plugin(FuelPlugin)
+        .add_plugin(HeatPlugin)
         .add_plugin(HudPlugin)
-        .add_plugin(LaserPlugin)
         .add_plugin(MenuPlugin)
+        .add_plugin(MissilePlugin)
+        .add_plugin(OrePlugin)
         .add_plugin(PlayerShipPlugin)
-        .add_plugin(StatesPlugin);
+        .add_plugin(PowerPlugin)
+        .add_plugin(ProjectilePlugin)
+        .add_plugin(RoomsPlugin)
+        .add_plugin(ShieldPlugin)
+        .add_plugin(StatesPlugin)
+        .add_plugin(StructurePlugin)
+        .add_plugin(TractorPlugin)
+        .add_plugin(TurretPlugin)
+        .add_plugin(WavePlugin);
 
     app.add_state(AppState::StartMenu)
         .add_state(AppGameState::Invalid)
@@ -0,0 +1,130 @@
+use crate::prelude::*;
+
+/// Duration s the blast sensor of a detonation stays in the world
+pub const BLAST_DURATION: f32 = 0.1;
+
+/// Self propelled projectile, steering toward hostile targets
+#[derive(Component)]
+pub struct Missile {
+    pub kind: WeaponKind,
+}
+
+/// Sensor dealing damage to everything it overlaps
+#[derive(Component)]
+pub struct Blast {
+    pub damage: u32,
+    /// Ship that fired the missile, spared by the blast
+    pub shooter: Entity,
+    pub timer: Timer,
+}
+
+/// Sent when a missile blows up
+pub struct DetonationEvent {
+    pub position: Vec2,
+    pub radius: f32,
+    pub damage: u32,
+    pub shooter: Entity,
+}
+
+pub struct MissilePlugin;
+
+impl Plugin for MissilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DetonationEvent>().add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(missile_guidance_system)
+                .with_system(spawn_blast)
+                .with_system(blast_timeout_system),
+        );
+    }
+}
+
+// Missiles turn toward the nearest hostile target in front of them,
+// and detonate once close enough to it.
+fn missile_guidance_system(
+    time: Res<Time>,
+    mut projectile_despawn_events: EventWriter<ProjectileDespawnEvent>,
+    mut missiles: Query<(Entity, &Missile, &Projectile, &Transform, &mut Velocity)>,
+    asteroids: Query<&Transform, With<Asteroid>>,
+    ships: Query<(Entity, &Transform), With<Ship>>,
+    factions: Factions,
+) {
+    for (entity, missile, projectile, transform, mut velocity) in missiles.iter_mut() {
+        let Some(guidance) = &missile.kind.definition().missile else {
+            continue;
+        };
+        let position = transform.translation.truncate();
+        let heading = (transform.rotation * Vec3::Y).truncate();
+        let hostiles = asteroids.iter().chain(
+            ships
+                .iter()
+                .filter(|(ship, _)| factions.hostile(projectile.shooter, *ship))
+                .map(|(_, transform)| transform),
+        );
+        let target = hostiles
+            .map(|transform| transform.translation.truncate() - position)
+            .filter(|offset| {
+                offset.length() <= guidance.seek_range
+                    && heading.angle_between(*offset).abs() <= guidance.seek_cone
+            })
+            .min_by(|a, b| a.length().total_cmp(&b.length()));
+
+        velocity.angvel = 0.0;
+        if let Some(offset) = target {
+            if offset.length() <= guidance.fuse_radius {
+                projectile_despawn_events.send(ProjectileDespawnEvent(entity));
+                continue;
+            }
+            let turn = heading.angle_between(offset);
+            velocity.angvel = turn.signum()
+                * guidance
+                    .turn_rate
+                    .min(turn.abs() / time.delta_seconds().max(f32::EPSILON));
+        }
+        velocity.linvel = (velocity.linvel
+            + heading * guidance.acceleration * time.delta_seconds())
+        .clamp_length_max(guidance.max_speed);
+    }
+}
+
+fn spawn_blast(
+    mut commands: Commands,
+    mut detonation_events: EventReader<DetonationEvent>,
+    mut explosion_spawn_events: EventWriter<ExplosionSpawnEvent>,
+) {
+    for detonation in detonation_events.iter() {
+        commands.spawn((
+            Blast {
+                damage: detonation.damage,
+                shooter: detonation.shooter,
+                timer: Timer::from_seconds(BLAST_DURATION, TimerMode::Once),
+            },
+            TransformBundle::from_transform(Transform::from_translation(
+                detonation.position.extend(0.0),
+            )),
+            ForState {
+                states: vec![AppState::Game],
+            },
+            Collider::ball(detonation.radius),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+        ));
+        explosion_spawn_events.send(ExplosionSpawnEvent {
+            kind: ExplosionKind::MissileBlast,
+            position: detonation.position,
+        });
+    }
+}
+
+fn blast_timeout_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut blasts: Query<(Entity, &mut Blast)>,
+) {
+    for (entity, mut blast) in blasts.iter_mut() {
+        blast.timer.tick(time.delta());
+        if blast.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
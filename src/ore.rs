@@ -0,0 +1,134 @@
+use std::f32::consts::PI;
+
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+/// Ore available when a game starts
+pub const START_ORE: u32 = 40;
+/// Ore carried by a single pickup
+pub const ORE_PER_PICKUP: u32 = 5;
+/// Duration s before an uncollected pickup vanishes
+pub const ORE_LIFETIME: f32 = 20.0;
+pub const ORE_RADIUS: f32 = 4.0;
+
+/// Ore mined by the player, spent on building parts
+#[derive(Debug, Resource)]
+pub struct Ore {
+    pub amount: u32,
+}
+
+impl Ore {
+    /// Pay a cost, false without effect when it can not be afforded
+    pub fn spend(&mut self, cost: u32) -> bool {
+        if self.amount < cost {
+            return false;
+        }
+        self.amount -= cost;
+        true
+    }
+}
+
+#[derive(Component)]
+pub struct OrePickup {
+    pub amount: u32,
+    pub despawn_timer: Timer,
+}
+
+/// Sent when an asteroid breaks, scattering ore around its position
+pub struct OreSpawnEvent {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub pickups: u32,
+}
+
+/// Sent when a collector touches a pickup
+pub struct OreCollectEvent(pub Entity);
+
+pub struct OrePlugin;
+
+impl Plugin for OrePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<OreSpawnEvent>()
+            .add_event::<OreCollectEvent>()
+            .add_system_set(SystemSet::on_enter(AppState::Game).with_system(ore_setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(spawn_ore)
+                    .with_system(ore_collect_system.after(ContactResolution))
+                    .with_system(ore_timeout_system),
+            );
+    }
+}
+
+fn ore_setup(mut commands: Commands) {
+    commands.insert_resource(Ore { amount: START_ORE });
+}
+
+fn spawn_ore(mut commands: Commands, mut ore_spawn_events: EventReader<OreSpawnEvent>) {
+    let shape = RegularPolygon {
+        sides: 4,
+        feature: shapes::RegularPolygonFeature::Radius(ORE_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    let mut rng = thread_rng();
+    for spawn_event in ore_spawn_events.iter() {
+        for _ in 0..spawn_event.pickups {
+            let spread = Vec2::from_angle(rng.gen_range(0.0..2.0 * PI));
+            commands.spawn((
+                OrePickup {
+                    amount: ORE_PER_PICKUP,
+                    despawn_timer: Timer::from_seconds(ORE_LIFETIME, TimerMode::Once),
+                },
+                GeometryBuilder::build_as(
+                    &shape,
+                    DrawMode::Fill(FillMode::color(Color::GOLD)),
+                    Transform::from_translation(
+                        (spawn_event.position + spread * ORE_RADIUS * 2.0).extend(1.5),
+                    ),
+                ),
+                ForState {
+                    states: vec![AppState::Game],
+                },
+                RigidBody::Dynamic,
+                Collider::ball(ORE_RADIUS),
+                ColliderMassProperties::Mass(0.1),
+                Sensor,
+                ActiveEvents::COLLISION_EVENTS,
+                Velocity {
+                    linvel: spawn_event.velocity + spread * rng.gen_range(10.0..30.0),
+                    angvel: rng.gen_range(-2.0..2.0),
+                },
+            ));
+        }
+    }
+}
+
+fn ore_collect_system(
+    mut commands: Commands,
+    mut ore_collect_events: EventReader<OreCollectEvent>,
+    mut ore: ResMut<Ore>,
+    pickups: Query<&OrePickup>,
+) {
+    // A pickup touching several collectors at once is only collected once
+    let collected: HashSet<Entity> = ore_collect_events.iter().map(|event| event.0).collect();
+    for entity in collected {
+        if let Ok(pickup) = pickups.get(entity) {
+            ore.amount += pickup.amount;
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn ore_timeout_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pickups: Query<(Entity, &mut OrePickup)>,
+) {
+    for (entity, mut pickup) in pickups.iter_mut() {
+        pickup.despawn_timer.tick(time.delta());
+        if pickup.despawn_timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
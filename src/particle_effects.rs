@@ -10,7 +10,9 @@ impl Plugin for ParticleEffectsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(HanabiPlugin)
             .add_system(add_thrust_particles_to_ship)
-            .add_system(update_thrust_particles);
+            .add_system(update_thrust_particles)
+            .add_system(add_explosion_particles)
+            .add_system(explosion_particles_timeout_system);
     }
 }
 
@@ -80,3 +82,74 @@ fn update_thrust_particles(
         }
     }
 }
+
+// Tag component for an explosion burst, removed once its particles are gone
+#[derive(Component)]
+pub struct ExplosionParticles(Timer);
+
+// Add a burst of sparks to every ship explosion
+fn add_explosion_particles(
+    mut commands: Commands,
+    mut effects: ResMut<Assets<EffectAsset>>,
+    mut effect: Local<Option<Handle<EffectAsset>>>,
+    added_explosions: Query<(&Explosion, &Transform), Added<Explosion>>,
+) {
+    for (explosion, transform) in added_explosions.iter() {
+        if explosion.kind != ExplosionKind::ShipDeath {
+            continue;
+        }
+        // The same burst is shared by every explosion
+        let effect = effect.get_or_insert_with(|| {
+            let mut gradient = Gradient::new();
+            gradient.add_key(0.0, Vec4::new(1.0, 1.0, 0.6, 1.0));
+            gradient.add_key(0.3, Vec4::new(1.0, 0.5, 0.0, 0.8));
+            gradient.add_key(1.0, Vec4::ZERO);
+            effects.add(
+                EffectAsset {
+                    name: "Explosion".to_string(),
+                    capacity: 1024,
+                    spawner: Spawner::once(200.0.into(), true),
+                    z_layer_2d: 10.0,
+                    ..Default::default()
+                }
+                .init(ParticleLifetimeModifier { lifetime: 0.6 })
+                .init(PositionCircleModifier {
+                    axis: Vec3::Z,
+                    radius: 5.0,
+                    speed: Value::Uniform((50.0, 300.0)),
+                    dimension: ShapeDimension::Volume,
+                    ..Default::default()
+                })
+                .render(ColorOverLifetimeModifier { gradient })
+                .render(SizeOverLifetimeModifier {
+                    gradient: Gradient::constant(Vec2::splat(3.)),
+                }),
+            )
+        });
+        // Not a child of the scaled explosion sprite, the burst outlives it
+        commands.spawn((
+            ParticleEffectBundle {
+                effect: ParticleEffect::new(effect.clone()),
+                transform: Transform::from_translation(transform.translation),
+                ..Default::default()
+            },
+            ExplosionParticles(Timer::from_seconds(1.0, TimerMode::Once)),
+            ForState {
+                states: vec![AppState::Game],
+            },
+        ));
+    }
+}
+
+fn explosion_particles_timeout_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ExplosionParticles)>,
+) {
+    for (entity, mut particles) in query.iter_mut() {
+        particles.0.tick(time.delta());
+        if particles.0.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,638 @@
+use bevy::ecs::query::WorldQuery;
+use bevy::ecs::system::EntityCommands;
+
+use crate::prelude::*;
+
+pub const WALL_WIDTH: f32 = 5.0;
+pub const WALL_HIT_POINTS: u32 = 4;
+pub const WALL_MASS: f32 = 1.0;
+pub const ENGINE_RADIUS: f32 = 5.0;
+pub const ENGINE_HIT_POINTS: u32 = 2;
+pub const ENGINE_MASS: f32 = 1.5;
+pub const ENGINE_THRUST: f32 = 60.0;
+pub const ENGINE_POWER: f32 = 20.0;
+pub const WEAPON_RADIUS: f32 = 4.0;
+pub const WEAPON_HIT_POINTS: u32 = 2;
+pub const WEAPON_MASS: f32 = 1.0;
+pub const REACTOR_POWER: f32 = 60.0;
+pub const RADIATOR_RADIUS: f32 = 5.0;
+pub const RADIATOR_HIT_POINTS: u32 = 2;
+pub const RADIATOR_MASS: f32 = 0.5;
+pub const COLLECTOR_RADIUS: f32 = 5.0;
+pub const COLLECTOR_HIT_POINTS: u32 = 2;
+pub const COLLECTOR_MASS: f32 = 0.5;
+pub const TRACTOR_RADIUS: f32 = 4.0;
+pub const TRACTOR_HIT_POINTS: u32 = 2;
+pub const TRACTOR_MASS: f32 = 1.0;
+pub const SHIELD_RADIUS: f32 = 5.0;
+pub const SHIELD_HIT_POINTS: u32 = 2;
+pub const SHIELD_MASS: f32 = 1.0;
+pub const MODULE_SIZE: f32 = 16.0;
+
+/// Design of a single ship part, in ship local coordinates.
+/// Every part carries its own collider, so that hits can be applied to the specific part,
+/// and the ship mass follows its design.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum ShipPart {
+    Wall { start: Vec2, end: Vec2 },
+    Engine { position: Vec2 },
+    Weapon { position: Vec2, kind: WeaponKind },
+    Turret { position: Vec2, kind: WeaponKind },
+    Radiator { position: Vec2 },
+    Collector { position: Vec2 },
+    Tractor { position: Vec2 },
+    Shield { position: Vec2 },
+    Module { kind: ModuleKind, position: Vec2 },
+}
+
+/// Modules can only be placed inside the rooms of a ship
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ModuleKind {
+    /// Required to fly, losing it ends the ship
+    Cockpit,
+    Reactor,
+    CargoHold,
+    FuelTank,
+}
+
+impl ModuleKind {
+    pub const ALL: [ModuleKind; 4] = [
+        ModuleKind::Cockpit,
+        ModuleKind::Reactor,
+        ModuleKind::CargoHold,
+        ModuleKind::FuelTank,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModuleKind::Cockpit => "cockpit",
+            ModuleKind::Reactor => "reactor",
+            ModuleKind::CargoHold => "cargo",
+            ModuleKind::FuelTank => "fuel",
+        }
+    }
+
+    /// Room area px2 used by the module
+    pub fn footprint(&self) -> f32 {
+        match self {
+            ModuleKind::Cockpit => 400.0,
+            ModuleKind::Reactor => 800.0,
+            ModuleKind::CargoHold => 400.0,
+            ModuleKind::FuelTank => 400.0,
+        }
+    }
+
+    /// Ore cost of building the module
+    pub fn cost(&self) -> u32 {
+        match self {
+            ModuleKind::Cockpit => 20,
+            ModuleKind::Reactor => 15,
+            ModuleKind::CargoHold => 5,
+            ModuleKind::FuelTank => 6,
+        }
+    }
+
+    fn hit_points(&self) -> u32 {
+        match self {
+            ModuleKind::Cockpit => 3,
+            ModuleKind::Reactor => 4,
+            ModuleKind::CargoHold => 3,
+            ModuleKind::FuelTank => 2,
+        }
+    }
+
+    fn mass(&self) -> f32 {
+        match self {
+            ModuleKind::Cockpit => 1.0,
+            ModuleKind::Reactor => 3.0,
+            ModuleKind::CargoHold => 2.0,
+            ModuleKind::FuelTank => FUEL_TANK_MASS,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            ModuleKind::Cockpit => Color::CYAN,
+            ModuleKind::Reactor => Color::YELLOW,
+            ModuleKind::CargoHold => Color::rgb(0.6, 0.4, 0.2),
+            ModuleKind::FuelTank => Color::GREEN,
+        }
+    }
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipEngine {
+    /// Engine thrust N
+    pub thrust: f32,
+    /// Power W drawn while thrusting
+    pub power: f32,
+    /// Fuel units burnt per second at full thrust
+    pub fuel: f32,
+}
+
+/// Weapon mount, every mount of the ship fires its own weapon
+#[derive(Component, Clone, Debug)]
+pub struct ShipWeapon {
+    pub kind: WeaponKind,
+    /// Power W drawn while firing
+    pub power: f32,
+    /// Time before the next shot
+    pub cooldown: Timer,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipRadiator {
+    /// Heat dissipated per second
+    pub cooling: f32,
+}
+
+/// Collects the ore pickups it touches
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipCollector {}
+
+/// Weapon mount rotating on its own, aiming within an arc around the ship forward direction
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipTurret {
+    /// Half angle rad of the arc the turret aims within
+    pub arc: f32,
+    /// Turn rate rad/s
+    pub turn_rate: f32,
+    /// Angle rad from the ship forward direction
+    pub angle: f32,
+}
+
+/// Tractor beam mount, towing what lies ahead of it
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipTractor {}
+
+/// Shield generator, the generators of a ship add up to its shield
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipShield {
+    /// Damage absorbed by a full shield
+    pub capacity: f32,
+    /// Strength regained per second
+    pub recharge: f32,
+    /// Power W drawn while recharging
+    pub power: f32,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ShipReactor {
+    /// Power W generated
+    pub output: f32,
+}
+
+/// State a part builds up in play, kept when the part is moved to another body
+#[derive(WorldQuery)]
+pub struct PartState {
+    pub hit_points: &'static HitPoints,
+    pub heat: Option<&'static Heat>,
+    pub fuel_tank: Option<&'static FuelTank>,
+    pub weapon: Option<&'static ShipWeapon>,
+    pub turret: Option<&'static ShipTurret>,
+}
+
+impl ShipPart {
+    /// Spawn the part as a child of a ship, or of any other rigid body.
+    pub fn spawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut part = match self {
+            ShipPart::Wall { start, end } => parent.spawn(wall_bundle(start, end)),
+            ShipPart::Engine { position } => parent.spawn(engine_bundle(position)),
+            ShipPart::Weapon { position, kind } => {
+                let mut weapon = parent.spawn(weapon_bundle(position, kind));
+                if kind.definition().beam.is_some() {
+                    weapon.insert(BeamEmitter::default());
+                }
+                weapon
+            }
+            ShipPart::Turret { position, kind } => {
+                let mut turret = parent.spawn(turret_bundle(position, kind));
+                if kind.definition().beam.is_some() {
+                    turret.insert(BeamEmitter::default());
+                }
+                turret
+            }
+            ShipPart::Radiator { position } => parent.spawn(radiator_bundle(position)),
+            ShipPart::Collector { position } => parent.spawn(collector_bundle(position)),
+            ShipPart::Tractor { position } => parent.spawn(tractor_bundle(position)),
+            ShipPart::Shield { position } => parent.spawn(shield_bundle(position)),
+            ShipPart::Module { kind, position } => {
+                let mut module = parent.spawn(module_bundle(kind, position));
+                match kind {
+                    ModuleKind::Reactor => {
+                        module.insert(ShipReactor {
+                            output: REACTOR_POWER,
+                        });
+                    }
+                    ModuleKind::FuelTank => {
+                        module.insert(FuelTank::full());
+                    }
+                    _ => {}
+                }
+                module
+            }
+        };
+        // Parts of the same ship never collide, whichever section they belong to
+        part.insert(ActiveHooks::FILTER_CONTACT_PAIRS);
+        part
+    }
+
+    /// Spawn the part again on another body, as it was on its previous one
+    pub fn respawn<'w, 's, 'a>(
+        self,
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
+        state: &PartStateItem,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut part = self.spawn(parent);
+        part.insert(*state.hit_points);
+        if let Some(heat) = state.heat {
+            part.insert(*heat);
+        }
+        if let Some(fuel_tank) = state.fuel_tank {
+            part.insert(*fuel_tank);
+        }
+        if let Some(weapon) = state.weapon {
+            part.insert(weapon.clone());
+        }
+        if let Some(turret) = state.turret {
+            part.insert(*turret);
+        }
+        part
+    }
+
+    pub fn center(&self) -> Vec2 {
+        match *self {
+            ShipPart::Wall { start, end } => (start + end) / 2.0,
+            ShipPart::Engine { position }
+            | ShipPart::Weapon { position, .. }
+            | ShipPart::Turret { position, .. }
+            | ShipPart::Radiator { position }
+            | ShipPart::Collector { position }
+            | ShipPart::Tractor { position }
+            | ShipPart::Shield { position }
+            | ShipPart::Module { position, .. } => position,
+        }
+    }
+
+    /// Color of the part when intact and cool
+    pub fn color(&self) -> Color {
+        match *self {
+            ShipPart::Wall { .. } => Color::WHITE,
+            ShipPart::Engine { .. } => Color::RED,
+            ShipPart::Weapon { .. } | ShipPart::Turret { .. } => Color::ORANGE,
+            ShipPart::Radiator { .. } => Color::SILVER,
+            ShipPart::Collector { .. } => Color::LIME_GREEN,
+            ShipPart::Tractor { .. } => Color::VIOLET,
+            ShipPart::Shield { .. } => Color::AZURE,
+            ShipPart::Module { kind, .. } => kind.color(),
+        }
+    }
+
+    /// Ore cost of building the part, walls cost by length
+    pub fn cost(&self) -> u32 {
+        match *self {
+            ShipPart::Wall { start, end } => (start.distance(end) / GRID_SIZE).ceil() as u32,
+            ShipPart::Engine { .. } => 8,
+            ShipPart::Weapon { kind, .. } => kind.definition().cost,
+            ShipPart::Turret { kind, .. } => kind.definition().cost + TURRET_COST,
+            ShipPart::Radiator { .. } => 5,
+            ShipPart::Collector { .. } => 6,
+            ShipPart::Tractor { .. } => 12,
+            ShipPart::Shield { .. } => 16,
+            ShipPart::Module { kind, .. } => kind.cost(),
+        }
+    }
+
+    /// Distance from the outline of the part to a point, in ship local coordinates
+    pub fn distance_to(&self, point: Vec2) -> f32 {
+        let distance = match *self {
+            ShipPart::Wall { start, end } => point_segment_distance(point, start, end),
+            _ => self.center().distance(point),
+        };
+        (distance - self.radius()).max(0.0)
+    }
+
+    fn radius(&self) -> f32 {
+        match *self {
+            ShipPart::Wall { .. } => WALL_WIDTH / 2.0,
+            ShipPart::Engine { .. } => ENGINE_RADIUS,
+            ShipPart::Weapon { .. } | ShipPart::Turret { .. } => WEAPON_RADIUS,
+            ShipPart::Radiator { .. } => RADIATOR_RADIUS,
+            ShipPart::Collector { .. } => COLLECTOR_RADIUS,
+            ShipPart::Tractor { .. } => TRACTOR_RADIUS,
+            ShipPart::Shield { .. } => SHIELD_RADIUS,
+            ShipPart::Module { .. } => MODULE_SIZE / 2.0,
+        }
+    }
+
+    /// Gap between the part and the others, it can still be attached to
+    fn reach(&self) -> f32 {
+        match *self {
+            ShipPart::Module { .. } => GRID_SIZE / 2.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Parts are attached to each other when they touch.
+    /// Modules are bolted to the walls and modules next to them, inside their room.
+    pub fn is_attached_to(&self, other: &ShipPart) -> bool {
+        let distance = match (*self, *other) {
+            (
+                ShipPart::Wall { start, end },
+                ShipPart::Wall {
+                    start: other_start,
+                    end: other_end,
+                },
+            ) => segment_distance(start, end, other_start, other_end),
+            (ShipPart::Wall { start, end }, part) | (part, ShipPart::Wall { start, end }) => {
+                point_segment_distance(part.center(), start, end)
+            }
+            (part, other) => part.center().distance(other.center()),
+        };
+        distance - self.radius() - other.radius() <= self.reach().max(other.reach())
+    }
+}
+
+/// Group parts that are attached to each other, directly or through other parts.
+/// Groups are returned as lists of indices into `parts`.
+pub fn connected_parts(parts: &[ShipPart]) -> Vec<Vec<usize>> {
+    let mut group_of = vec![None; parts.len()];
+    let mut groups = Vec::new();
+    for first in 0..parts.len() {
+        if group_of[first].is_some() {
+            continue;
+        }
+        let mut group = vec![first];
+        group_of[first] = Some(groups.len());
+        let mut next = 0;
+        while next < group.len() {
+            let current = group[next];
+            for (other, part) in parts.iter().enumerate() {
+                if group_of[other].is_none() && parts[current].is_attached_to(part) {
+                    group_of[other] = Some(groups.len());
+                    group.push(other);
+                }
+            }
+            next += 1;
+        }
+        groups.push(group);
+    }
+    groups
+}
+
+/// The ship core is its cockpit.
+/// Without one, the part closest to the ship origin is used instead.
+pub fn core_part(parts: &[ShipPart]) -> Option<usize> {
+    parts
+        .iter()
+        .position(|part| {
+            matches!(
+                part,
+                ShipPart::Module {
+                    kind: ModuleKind::Cockpit,
+                    ..
+                }
+            )
+        })
+        .or_else(|| {
+            parts
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    a.distance_to(Vec2::ZERO)
+                        .total_cmp(&b.distance_to(Vec2::ZERO))
+                })
+                .map(|(index, _)| index)
+        })
+}
+
+fn wall_bundle(start: Vec2, end: Vec2) -> impl Bundle {
+    let mut path_builder = PathBuilder::new();
+    path_builder.move_to(start);
+    path_builder.line_to(end);
+    (
+        ShipPart::Wall { start, end },
+        HitPoints::new(WALL_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &path_builder.build(),
+            DrawMode::Stroke(StrokeMode::new(Color::WHITE, WALL_WIDTH)),
+            Transform::default(),
+        ),
+        Collider::capsule(start, end, WALL_WIDTH / 2.0),
+        ColliderMassProperties::Mass(WALL_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn engine_bundle(position: Vec2) -> impl Bundle {
+    let engines = RegularPolygon {
+        sides: 4,
+        feature: shapes::RegularPolygonFeature::Radius(ENGINE_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    (
+        ShipPart::Engine { position },
+        ShipEngine {
+            thrust: ENGINE_THRUST,
+            power: ENGINE_POWER,
+            fuel: ENGINE_FUEL_BURN,
+        },
+        HitPoints::new(ENGINE_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&engines),
+            DrawMode::Fill(FillMode::color(Color::RED)),
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(ENGINE_RADIUS),
+        ColliderMassProperties::Mass(ENGINE_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn weapon_bundle(position: Vec2, kind: WeaponKind) -> impl Bundle {
+    let weapon = RegularPolygon {
+        sides: 3,
+        feature: shapes::RegularPolygonFeature::Radius(WEAPON_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    (
+        ShipPart::Weapon { position, kind },
+        ShipWeapon {
+            kind,
+            power: kind.definition().power,
+            cooldown: Timer::from_seconds(kind.definition().interval, TimerMode::Once),
+        },
+        HitPoints::new(WEAPON_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&weapon),
+            DrawMode::Fill(FillMode::color(Color::ORANGE)),
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(WEAPON_RADIUS),
+        ColliderMassProperties::Mass(WEAPON_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn turret_bundle(position: Vec2, kind: WeaponKind) -> impl Bundle {
+    let turret = RegularPolygon {
+        sides: 3,
+        feature: shapes::RegularPolygonFeature::Radius(WEAPON_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    (
+        ShipPart::Turret { position, kind },
+        ShipWeapon {
+            kind,
+            power: kind.definition().power,
+            cooldown: Timer::from_seconds(kind.definition().interval, TimerMode::Once),
+        },
+        ShipTurret {
+            arc: TURRET_ARC,
+            turn_rate: TURRET_TURN_RATE,
+            angle: 0.0,
+        },
+        HitPoints::new(WEAPON_HIT_POINTS),
+        Heat::default(),
+        // Outlined to tell it from the fixed mounts
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&turret),
+            DrawMode::Outlined {
+                fill_mode: FillMode::color(Color::ORANGE),
+                outline_mode: StrokeMode::new(Color::WHITE, 1.0),
+            },
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(WEAPON_RADIUS),
+        ColliderMassProperties::Mass(WEAPON_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn radiator_bundle(position: Vec2) -> impl Bundle {
+    let radiator = RegularPolygon {
+        sides: 6,
+        feature: shapes::RegularPolygonFeature::Radius(RADIATOR_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    (
+        ShipPart::Radiator { position },
+        ShipRadiator {
+            cooling: RADIATOR_COOLING,
+        },
+        HitPoints::new(RADIATOR_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&radiator),
+            DrawMode::Fill(FillMode::color(Color::SILVER)),
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(RADIATOR_RADIUS),
+        ColliderMassProperties::Mass(RADIATOR_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn collector_bundle(position: Vec2) -> impl Bundle {
+    let collector = RegularPolygon {
+        sides: 5,
+        feature: shapes::RegularPolygonFeature::Radius(COLLECTOR_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    (
+        ShipPart::Collector { position },
+        ShipCollector {},
+        HitPoints::new(COLLECTOR_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&collector),
+            DrawMode::Fill(FillMode::color(Color::LIME_GREEN)),
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(COLLECTOR_RADIUS),
+        ColliderMassProperties::Mass(COLLECTOR_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn tractor_bundle(position: Vec2) -> impl Bundle {
+    let tractor = shapes::Circle {
+        radius: TRACTOR_RADIUS,
+        center: Vec2::ZERO,
+    };
+    (
+        ShipPart::Tractor { position },
+        ShipTractor {},
+        HitPoints::new(TRACTOR_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&tractor),
+            DrawMode::Fill(FillMode::color(Color::VIOLET)),
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(TRACTOR_RADIUS),
+        ColliderMassProperties::Mass(TRACTOR_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn shield_bundle(position: Vec2) -> impl Bundle {
+    let shield = RegularPolygon {
+        sides: 8,
+        feature: shapes::RegularPolygonFeature::Radius(SHIELD_RADIUS),
+        ..shapes::RegularPolygon::default()
+    };
+    (
+        ShipPart::Shield { position },
+        ShipShield {
+            capacity: SHIELD_CAPACITY,
+            recharge: SHIELD_RECHARGE,
+            power: SHIELD_POWER,
+        },
+        HitPoints::new(SHIELD_HIT_POINTS),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &ShapePath::build_as(&shield),
+            DrawMode::Fill(FillMode::color(Color::AZURE)),
+            Transform::from_translation(position.extend(0.0)),
+        ),
+        Collider::ball(SHIELD_RADIUS),
+        ColliderMassProperties::Mass(SHIELD_MASS),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
+
+fn module_bundle(kind: ModuleKind, position: Vec2) -> impl Bundle {
+    let shape = shapes::Rectangle {
+        extents: Vec2::splat(MODULE_SIZE),
+        origin: RectangleOrigin::Center,
+    };
+    (
+        ShipPart::Module { kind, position },
+        HitPoints::new(kind.hit_points()),
+        Heat::default(),
+        GeometryBuilder::build_as(
+            &shape,
+            DrawMode::Fill(FillMode::color(kind.color())),
+            Transform::from_translation(position.extend(0.1)),
+        ),
+        Collider::cuboid(MODULE_SIZE / 2.0, MODULE_SIZE / 2.0),
+        ColliderMassProperties::Mass(kind.mass()),
+        ActiveEvents::COLLISION_EVENTS | ActiveEvents::CONTACT_FORCE_EVENTS,
+        ContactForceEventThreshold(IMPACT_FORCE_THRESHOLD),
+    )
+}
@@ -1,6 +1,8 @@
 use crate::prelude::*;
 
 pub const START_LIFE: u32 = 3;
+/// Duration s a respawned ship can not be damaged
+pub const INVULNERABLE_DURATION: f32 = 3.0;
 
 // Actions are divided in two enums
 // One for pure Player Ship actions, during effective gameplay, added on the player entity itself.
@@ -11,6 +13,9 @@ pub enum PlayerAction {
     RotateLeft,
     RotateRight,
     Fire,
+    Tractor,
+    ToggleTurretMode,
+    CycleFlightAssist,
 
     ToggleBuild,
 }
@@ -19,25 +24,22 @@ pub enum PlayerAction {
 pub struct Ship {
     /// Ship rotation speed in rad/s
     pub rotation_speed: f32,
-    /// Ship thrust N
+    /// Ship thrust N, sum of the thrust of its working engines
     pub thrust: f32,
+    /// Share of the thrust applied this frame 0..1
+    pub throttle: f32,
     /// Ship life points
     pub life: u32,
-    /// Cannon auto-fire timer
-    pub cannon_timer: Timer,
-    /// Id of the controlling player. 1 or 2
+    /// Id of the controlling player. 1 or 2, 0 for enemy ships
     pub player_id: u32,
 }
 
+/// Respawned ships can not be damaged for a while
 #[derive(Component)]
-pub struct ShipWalls {}
-
-#[derive(Component)]
-pub struct ShipEngine {}
-
-#[derive(Component, Clone, Copy)]
-pub struct Damage {
-    pub value: u32,
+pub struct Invulnerable {
+    pub timer: Timer,
+    /// Visibility toggle timer
+    pub blink: Timer,
 }
 
 pub struct PlayerShipPlugin;
@@ -49,9 +51,15 @@ impl Plugin for PlayerShipPlugin {
             .add_system_set(
                 SystemSet::on_update(AppState::Game)
                     .with_system(build_toggle_system)
-                    .with_system(ship_input_system)
+                    .with_system(ship_input_system.label(FlightControl))
+                    .with_system(ship_engines_system)
                     .with_system(ship_dampening_system)
+                    .with_system(ship_loss_system)
+                    .with_system(ship_invulnerability_system)
                     .with_system(ship_timers_system),
+            )
+            .add_system_set(
+                SystemSet::on_exit(PlayerState::Building).with_system(blueprint_save_system),
             );
     }
 }
@@ -70,9 +78,15 @@ fn spawn_ship(mut commands: Commands) {
         (KeyCode::D, PlayerAction::RotateRight),
         (KeyCode::Right, PlayerAction::RotateRight),
         (KeyCode::Space, PlayerAction::Fire),
+        (KeyCode::T, PlayerAction::Tractor),
+        (KeyCode::R, PlayerAction::ToggleTurretMode),
+        (KeyCode::Q, PlayerAction::CycleFlightAssist),
         (KeyCode::F, PlayerAction::ToggleBuild),
     ]);
     input_map.insert(GamepadButtonType::South, PlayerAction::Fire);
+    input_map.insert(GamepadButtonType::West, PlayerAction::Tractor);
+    input_map.insert(GamepadButtonType::North, PlayerAction::ToggleTurretMode);
+    input_map.insert(GamepadButtonType::East, PlayerAction::CycleFlightAssist);
     input_map.insert(
         SingleAxis::positive_only(GamepadAxisType::LeftStickY, 0.4),
         PlayerAction::Forward,
@@ -90,71 +104,304 @@ fn spawn_ship(mut commands: Commands) {
         PlayerAction::RotateLeft,
     );
 
+    let blueprint = starter_blueprint();
     commands
         .spawn((
-            Ship {
-                rotation_speed: 3.0,
-                thrust: 60.0,
-                life: START_LIFE,
-                cannon_timer: Timer::from_seconds(0.2, TimerMode::Once),
-                player_id: 1,
-            },
-            ForState {
-                states: vec![AppState::Game],
-            },
-            RigidBody::Dynamic,
-            Collider::ball(13.5),
-            ExternalImpulse::default(),
-            Velocity::linear(Vec2::ZERO),
-            ActiveEvents::COLLISION_EVENTS,
+            ship_bundle(1, START_LIFE, Transform::default(), Velocity::zero()),
+            TurretMode::Cursor,
+            FlightAssist::Off,
             InputManagerBundle::<PlayerAction> {
                 action_state: ActionState::default(),
                 input_map,
             },
-            GeometryBuilder::build_as(
-                &PathBuilder::new().build(),
-                DrawMode::Stroke(StrokeMode::new(Color::WHITE, 5.0)),
-                Transform::default(),
-            ),
         ))
         .with_children(|parent| {
-            let mut walls_path = PathBuilder::new();
-            walls_path.move_to(Vec2::new(-5.0, -5.0));
-            walls_path.line_to(Vec2::new(-5.0, 5.0));
-            walls_path.line_to(Vec2::new(5.0, 5.0));
-            walls_path.line_to(Vec2::new(5.0, -5.0));
-            walls_path.line_to(Vec2::new(-5.0, -5.0));
-
-            parent.spawn((
-                ShipWalls {},
-                GeometryBuilder::build_as(
-                    &walls_path.build(),
-                    DrawMode::Stroke(StrokeMode::new(Color::WHITE, 5.0)),
-                    Transform::default(),
+            for part in blueprint.parts.iter() {
+                part.spawn(parent);
+            }
+        })
+        .insert(blueprint);
+}
+
+/// Components shared by every ship, whoever flies it.
+/// Parts are spawned as its children, and the ship is assembled from them.
+pub fn ship_bundle(
+    player_id: u32,
+    life: u32,
+    transform: Transform,
+    velocity: Velocity,
+) -> impl Bundle {
+    (
+        Ship {
+            rotation_speed: 3.0,
+            thrust: ENGINE_THRUST,
+            throttle: 0.0,
+            life,
+            player_id,
+        },
+        ForState {
+            states: vec![AppState::Game],
+        },
+        PowerGrid::default(),
+        FuelGauge::default(),
+        Shield::default(),
+        Autopilot::default(),
+        Unassembled,
+        RigidBody::Dynamic,
+        ExternalImpulse::default(),
+        velocity,
+        GeometryBuilder::build_as(
+            &PathBuilder::new().build(),
+            DrawMode::Stroke(StrokeMode::new(Color::WHITE, 5.0)),
+            transform,
+        ),
+    )
+}
+
+fn starter_blueprint() -> Blueprint {
+    let corners = [
+        Vec2::new(-40.0, -20.0),
+        Vec2::new(-40.0, 20.0),
+        Vec2::new(40.0, 20.0),
+        Vec2::new(40.0, -20.0),
+    ];
+    let mut parts: Vec<ShipPart> = corners
+        .iter()
+        .zip(corners.iter().cycle().skip(1))
+        .map(|(start, end)| ShipPart::Wall {
+            start: *start,
+            end: *end,
+        })
+        .collect();
+    parts.extend([
+        ShipPart::Engine {
+            position: Vec2::new(0.0, -20.0),
+        },
+        ShipPart::Weapon {
+            position: Vec2::new(0.0, 20.0),
+            kind: WeaponKind::Laser,
+        },
+        ShipPart::Radiator {
+            position: Vec2::new(-40.0, 0.0),
+        },
+        ShipPart::Radiator {
+            position: Vec2::new(40.0, 0.0),
+        },
+        ShipPart::Collector {
+            position: Vec2::new(20.0, 20.0),
+        },
+        ShipPart::Module {
+            kind: ModuleKind::Cockpit,
+            position: Vec2::ZERO,
+        },
+        ShipPart::Module {
+            kind: ModuleKind::Reactor,
+            position: Vec2::new(20.0, 0.0),
+        },
+        ShipPart::Module {
+            kind: ModuleKind::FuelTank,
+            position: Vec2::new(-20.0, 0.0),
+        },
+    ]);
+    Blueprint { parts }
+}
+
+// Keep the design built by the player, to restore it when the ship is lost.
+// It is saved as well, apart from the enemy designs, to be shared or added to them by hand.
+fn blueprint_save_system(
+    mut ships: Query<(&mut Blueprint, &Children), Without<Enemy>>,
+    parts: Query<&ShipPart>,
+) {
+    for (mut blueprint, children) in ships.iter_mut() {
+        blueprint.parts = parts.iter_many(children).copied().collect();
+        save_blueprint(PLAYER_BLUEPRINT_PATH, &blueprint);
+    }
+}
+
+// A ship whose cockpit is destroyed explodes, its remaining parts left drifting as debris.
+// The ship is rebuilt from its blueprint at the arena center while it has lives left.
+// Enemy ships are not rebuilt, destroying them scores.
+fn ship_loss_system(
+    mut commands: Commands,
+    mut gamestate: ResMut<State<AppGameState>>,
+    mut arena: ResMut<Arena>,
+    mut explosion_spawn_events: EventWriter<ExplosionSpawnEvent>,
+    mut ships: Query<(
+        Entity,
+        &Ship,
+        &Blueprint,
+        &Children,
+        &mut Transform,
+        &mut Velocity,
+    )>,
+    sections: Query<(Entity, &ShipSection, &Children)>,
+    parts: Query<(Entity, &ShipPart, PartState)>,
+    joints: Query<(Entity, &StructuralJoint, &ImpulseJoint, &Parent)>,
+    enemies: Query<(), With<Enemy>>,
+) {
+    for (ship_entity, ship, blueprint, children, mut transform, mut velocity) in ships.iter_mut() {
+        let ship_sections: Vec<_> = sections
+            .iter()
+            .filter(|(_, section, _)| section.ship == ship_entity)
+            .collect();
+        let has_cockpit = parts
+            .iter_many(
+                children.iter().chain(
+                    ship_sections
+                        .iter()
+                        .flat_map(|(_, _, children)| children.iter()),
                 ),
-            ));
+            )
+            .any(|(_, part, _)| {
+                matches!(
+                    part,
+                    ShipPart::Module {
+                        kind: ModuleKind::Cockpit,
+                        ..
+                    }
+                )
+            });
+        if has_cockpit {
+            continue;
+        }
+        explosion_spawn_events.send(ExplosionSpawnEvent {
+            kind: ExplosionKind::ShipDeath,
+            position: transform.translation.truncate(),
+        });
+        for (section_entity, _, _) in ship_sections {
+            commands
+                .entity(section_entity)
+                .remove::<ShipSection>()
+                .remove::<ExternalImpulse>()
+                .insert(Debris);
+        }
 
-            let engines = RegularPolygon {
-                sides: 4,
-                feature: shapes::RegularPolygonFeature::Radius(5.0),
-                ..shapes::RegularPolygon::default()
-            };
+        let enemy = enemies.contains(ship_entity);
+        if ship.life == 0 || enemy {
+            commands
+                .entity(ship_entity)
+                .remove::<Ship>()
+                .remove::<Unassembled>()
+                .remove::<ExternalImpulse>()
+                .remove::<Shield>()
+                .remove::<Autopilot>()
+                .remove::<Enemy>()
+                .remove::<InputManagerBundle<PlayerAction>>()
+                .insert(Debris);
+            if enemy {
+                arena.score += ENEMY_SCORE;
+            } else {
+                gamestate.set(AppGameState::GameOver).unwrap();
+            }
+            continue;
+        }
 
-            parent.spawn((
-                ShipEngine {},
+        // The wreck of the root body keeps drifting as debris
+        let wreck: Vec<_> = parts.iter_many(children).collect();
+        let wreck_entity = commands
+            .spawn((
+                Debris,
+                ForState {
+                    states: vec![AppState::Game],
+                },
+                RigidBody::Dynamic,
+                *velocity,
                 GeometryBuilder::build_as(
-                    &ShapePath::build_as(&engines),
-                    DrawMode::Stroke(StrokeMode::new(Color::RED, 5.0)),
-                    Transform {
-                        translation: Vec3::new(0.0, -5.0, 0.0),
-                        ..Default::default()
-                    },
+                    &PathBuilder::new().build(),
+                    DrawMode::Stroke(StrokeMode::new(Color::WHITE, WALL_WIDTH)),
+                    *transform,
                 ),
-            ));
-        });
+            ))
+            .with_children(|parent| {
+                for (_, part, state) in wreck.iter() {
+                    part.respawn(parent, state);
+                }
+            })
+            .id();
+        // Sections jointed to the root stay jointed to its wreck, not to the respawned ship.
+        // Joints can not be moved to another body, they are rebuilt.
+        for (joint_entity, structural_joint, joint, parent) in joints.iter() {
+            if joint.parent != ship_entity {
+                continue;
+            }
+            commands.entity(joint_entity).despawn_recursive();
+            if !wreck.is_empty() {
+                commands.entity(parent.get()).with_children(|parent| {
+                    parent.spawn((
+                        StructuralJoint {
+                            strength: structural_joint.strength,
+                        },
+                        ImpulseJoint::new(wreck_entity, joint.data),
+                    ));
+                });
+            }
+        }
+        for (entity, _, _) in wreck {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        // Respawn, rebuilt from the blueprint
+        *transform = Transform::default();
+        *velocity = Velocity::zero();
+        commands
+            .entity(ship_entity)
+            .insert((
+                Unassembled,
+                Invulnerable {
+                    timer: Timer::from_seconds(INVULNERABLE_DURATION, TimerMode::Once),
+                    blink: Timer::from_seconds(0.1, TimerMode::Repeating),
+                },
+            ))
+            .with_children(|parent| {
+                for part in blueprint.parts.iter() {
+                    part.spawn(parent);
+                }
+            });
+    }
+}
+
+// Invulnerable ships blink until their invulnerability wears off, along with their sections
+fn ship_invulnerability_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ships: Query<(Entity, &mut Invulnerable, &mut Visibility)>,
+    mut sections: Query<(&ShipSection, &mut Visibility), Without<Invulnerable>>,
+) {
+    for (entity, mut invulnerable, mut visibility) in ships.iter_mut() {
+        invulnerable.timer.tick(time.delta());
+        invulnerable.blink.tick(time.delta());
+        if invulnerable.timer.finished() {
+            visibility.is_visible = true;
+            commands.entity(entity).remove::<Invulnerable>();
+        } else if invulnerable.blink.just_finished() {
+            visibility.is_visible = !visibility.is_visible;
+        }
+        for (section, mut section_visibility) in sections.iter_mut() {
+            if section.ship == entity {
+                section_visibility.is_visible = visibility.is_visible;
+            }
+        }
+    }
+}
+
+// Recompute the ship thrust as engines are built, destroyed, or overheat
+fn ship_engines_system(
+    mut ships: Query<(&mut Ship, &Children)>,
+    engines: Query<(&ShipEngine, &Heat)>,
+) {
+    for (mut ship, children) in ships.iter_mut() {
+        ship.thrust = engines
+            .iter_many(children)
+            .filter(|(_, heat)| !heat.overheated)
+            .map(|(engine, _)| engine.thrust)
+            .sum();
+    }
 }
 
-fn ship_dampening_system(time: Res<Time>, mut query: Query<&mut Velocity, With<Ship>>) {
+fn ship_dampening_system(
+    time: Res<Time>,
+    mut query: Query<&mut Velocity, Or<(With<Ship>, With<ShipSection>)>>,
+) {
     for mut velocity in query.iter_mut() {
         let elapsed = time.delta_seconds();
         velocity.angvel *= 0.1f32.powf(elapsed);
@@ -178,50 +425,237 @@ fn build_toggle_system(
 }
 
 fn ship_input_system(
+    time: Res<Time>,
     gamestate: Res<State<AppGameState>>,
     playerstate: ResMut<State<PlayerState>>,
-    mut laser_spawn_events: EventWriter<LaserSpawnEvent>,
+    rapier_context: Res<RapierContext>,
+    mut projectile_spawn_events: EventWriter<ProjectileSpawnEvent>,
     mut query: Query<(
+        Entity,
         &ActionState<PlayerAction>,
         &mut ExternalImpulse,
         &mut Velocity,
         &Transform,
         &mut Ship,
+        &mut PowerGrid,
+        &mut FuelGauge,
+        &Autopilot,
+        Option<&Enemy>,
+    )>,
+    mut sections: Query<
+        (
+            Entity,
+            &ShipSection,
+            &mut ExternalImpulse,
+            Option<&RapierRigidBodyHandle>,
+        ),
+        Without<Ship>,
+    >,
+    bodies: Query<&RapierRigidBodyHandle>,
+    engines: Query<(&ShipEngine, &Heat, &GlobalTransform, &Parent), Without<ShipWeapon>>,
+    mut weapons: Query<(
+        &GlobalTransform,
+        &Parent,
+        &mut ShipWeapon,
+        &mut Heat,
+        Option<&mut BeamEmitter>,
     )>,
 ) {
-    if gamestate.current() == &AppGameState::Game && playerstate.current() == &PlayerState::Flying {
-        for (action_state, mut impulse, mut velocity, transform, mut ship) in query.iter_mut() {
-            let thrust = if action_state.pressed(PlayerAction::Forward) {
-                1.0
+    let playing = gamestate.current() == &AppGameState::Game;
+    let flying = playerstate.current() == &PlayerState::Flying;
+    for (
+        entity,
+        action_state,
+        mut impulse,
+        mut velocity,
+        transform,
+        mut ship,
+        mut grid,
+        mut gauge,
+        autopilot,
+        enemy,
+    ) in query.iter_mut()
+    {
+        // Enemy ships keep flying while the player builds
+        if !playing || !(flying || enemy.is_some()) {
+            ship.throttle = 0.0;
+            continue;
+        }
+        let direction = (transform.rotation * Vec3::Y).truncate();
+        let delta = time.delta_seconds();
+        let scale = rapier_context.physics_scale();
+        // Mass, angular inertia and center of mass px of the bodies of the ship and its sections
+        let body_masses: Vec<(Entity, f32, f32, Vec2)> =
+            std::iter::once((entity, bodies.get(entity).ok()))
+                .chain(
+                    sections
+                        .iter()
+                        .filter(|(_, section, _, _)| section.ship == entity)
+                        .map(|(section_entity, _, _, handle)| (section_entity, handle)),
+                )
+                .filter_map(|(body, handle)| {
+                    let rigid_body = rapier_context.bodies.get(handle?.0)?;
+                    let props = rigid_body.mass_properties();
+                    let center = props.world_com(rigid_body.position());
+                    Some((
+                        body,
+                        props.mass(),
+                        props.principal_inertia() * scale * scale,
+                        Vec2::new(center.x, center.y) * scale,
+                    ))
+                })
+                .collect();
+        // Engines of every section push the whole ship, as long as its joints hold,
+        // each at its own position
+        let pushes: Vec<(Entity, Vec2, Vec2)> = engines
+            .iter()
+            .filter(|(_, heat, _, parent)| {
+                !heat.overheated && body_masses.iter().any(|(body, ..)| *body == parent.get())
+            })
+            .map(|(engine, _, engine_transform, parent)| {
+                (
+                    parent.get(),
+                    engine_transform.translation().truncate(),
+                    direction * engine.thrust,
+                )
+            })
+            .collect();
+        let mass: f32 = body_masses.iter().map(|(_, mass, _, _)| mass).sum();
+        let center = body_masses
+            .iter()
+            .map(|(_, mass, _, center)| *center * *mass)
+            .sum::<Vec2>()
+            / mass.max(f32::EPSILON);
+        let inertia: f32 = body_masses
+            .iter()
+            .map(|(_, mass, inertia, body_center)| {
+                inertia + mass * body_center.distance_squared(center)
+            })
+            .sum();
+        let push: Vec2 = pushes.iter().map(|(_, _, push)| *push).sum();
+        let torque: f32 = pushes
+            .iter()
+            .map(|(_, position, push)| (*position - center).perp_dot(*push))
+            .sum();
+        // Impulses are applied once per frame
+        let state = FlightState {
+            heading: direction,
+            angvel: velocity.angvel,
+            linvel: velocity.linvel,
+            acceleration: if mass > 0.0 && delta > 0.0 {
+                push.length() / (mass * delta)
             } else {
                 0.0
-            };
-            let rotation = if action_state.pressed(PlayerAction::RotateLeft) {
-                1
-            } else if action_state.pressed(PlayerAction::RotateRight) {
-                -1
+            },
+            spin: if inertia > 0.0 && delta > 0.0 {
+                torque / (inertia * delta)
             } else {
-                0
-            };
-            let fire = action_state.pressed(PlayerAction::Fire);
-            if rotation != 0 {
-                velocity.angvel = rotation as f32 * ship.rotation_speed;
+                0.0
+            },
+        };
+        // The pilot overrides the autopilot on the controls they hold
+        let thrust = if action_state.pressed(PlayerAction::Forward) {
+            1.0
+        } else if autopilot.engaged {
+            steer_thrust(autopilot, &state)
+        } else {
+            0.0
+        };
+        // Engines stop working once the tanks are empty
+        let thrust = if gauge.fuel > 0.0 { thrust } else { 0.0 };
+        let fire = action_state.pressed(PlayerAction::Fire);
+        // Engines, weapons and shields in use share the reactors output
+        grid.demand = grid.engines * thrust + grid.shields + if fire { grid.weapons } else { 0.0 };
+        let power = grid.satisfaction();
+        gauge.burn = gauge.engines * thrust * power;
+        ship.throttle = thrust * power;
+        let turn = if action_state.pressed(PlayerAction::RotateLeft) {
+            1.0
+        } else if action_state.pressed(PlayerAction::RotateRight) {
+            -1.0
+        } else if autopilot.engaged {
+            steer_turn(autopilot, &state, thrust * power)
+        } else {
+            0.0
+        };
+        if turn != 0.0 {
+            // Gyros speed up the spin no further than the ship rotation speed
+            let limit = ship.rotation_speed.max(velocity.angvel.abs());
+            velocity.angvel =
+                (velocity.angvel + turn * ROTATION_ACCELERATION * delta).clamp(-limit, limit);
+        }
+        // Engines push their own body, turning it as they push off its center of mass.
+        // Rapier takes the torque impulse in meters, unlike the impulse.
+        let body_impulse = |body: Entity| {
+            let body_center = body_masses
+                .iter()
+                .find(|(mass_body, ..)| *mass_body == body)
+                .map_or(center, |(_, _, _, body_center)| *body_center);
+            pushes
+                .iter()
+                .filter(|(push_body, _, _)| *push_body == body)
+                .fold(ExternalImpulse::default(), |sum, (_, position, push)| {
+                    let push = *push * thrust * power;
+                    ExternalImpulse {
+                        impulse: sum.impulse + push,
+                        torque_impulse: sum.torque_impulse
+                            + (*position - body_center).perp_dot(push) / (scale * scale),
+                    }
+                })
+        };
+        *impulse = body_impulse(entity);
+        for (section_entity, section, mut section_impulse, _) in sections.iter_mut() {
+            if section.ship == entity {
+                *section_impulse = body_impulse(section_entity);
             }
-            impulse.impulse = (transform.rotation * (Vec3::Y * thrust * ship.thrust)).truncate();
+        }
 
-            if fire && ship.cannon_timer.finished() {
-                laser_spawn_events.send(LaserSpawnEvent {
-                    transform: *transform,
-                    velocity: *velocity,
-                });
-                ship.cannon_timer.reset();
+        if fire {
+            // Every loaded weapon mount of the ship fires, including those of its sections
+            for (weapon_transform, parent, mut weapon, mut heat, beam) in weapons.iter_mut() {
+                let ship_entity = sections
+                    .get(parent.get())
+                    .map_or(parent.get(), |(_, section, _, _)| section.ship);
+                if ship_entity != entity || heat.overheated {
+                    continue;
+                }
+                // Beams keep firing as long as the trigger is held
+                if let Some(mut beam) = beam {
+                    beam.firing = true;
+                } else if weapon.cooldown.finished() {
+                    heat.add(weapon.kind.definition().heat);
+                    weapon.cooldown.reset();
+                    // Projectiles inherit the motion of the mount, spin of its body included
+                    let muzzle = weapon_transform.translation().truncate();
+                    let muzzle_velocity = bodies
+                        .get(parent.get())
+                        .ok()
+                        .and_then(|body| body_velocity_at_point(&rapier_context, body.0, muzzle))
+                        .unwrap_or(velocity.linvel);
+                    projectile_spawn_events.send(ProjectileSpawnEvent {
+                        weapon: weapon.kind,
+                        transform: weapon_transform.compute_transform(),
+                        velocity: muzzle_velocity,
+                        shooter: entity,
+                    });
+                }
             }
         }
     }
 }
 
-fn ship_timers_system(time: Res<Time>, mut ship: Query<&mut Ship>) {
-    for mut ship in ship.iter_mut() {
-        ship.cannon_timer.tick(time.delta());
+// Weapons reload slower during brownouts
+fn ship_timers_system(
+    time: Res<Time>,
+    grids: Query<&PowerGrid>,
+    sections: Query<&ShipSection>,
+    mut weapons: Query<(&mut ShipWeapon, &Parent)>,
+) {
+    for (mut weapon, parent) in weapons.iter_mut() {
+        let ship_entity = sections
+            .get(parent.get())
+            .map_or(parent.get(), |section| section.ship);
+        let satisfaction = grids.get(ship_entity).map_or(1.0, PowerGrid::satisfaction);
+        weapon.cooldown.tick(time.delta().mul_f32(satisfaction));
     }
 }
@@ -0,0 +1,72 @@
+use crate::prelude::*;
+
+/// Power balance of a ship, its reactors supplying the engines, weapons and shields in use
+#[derive(Component, Debug, Default)]
+pub struct PowerGrid {
+    /// Power W generated by the reactors
+    pub supply: f32,
+    /// Power W drawn by the engines at full thrust
+    pub engines: f32,
+    /// Power W drawn by the weapon mounts while firing
+    pub weapons: f32,
+    /// Power W drawn by the shield generators while recharging
+    pub shields: f32,
+    /// Power W drawn by the parts currently in use
+    pub demand: f32,
+}
+
+impl PowerGrid {
+    /// Share of the demand that can be supplied.
+    /// Parts drawing power are scaled down by it during brownouts.
+    pub fn satisfaction(&self) -> f32 {
+        if self.demand <= self.supply {
+            1.0
+        } else {
+            self.supply / self.demand
+        }
+    }
+}
+
+pub struct PowerPlugin;
+
+impl Plugin for PowerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_update(AppState::Game).with_system(power_grid_system));
+    }
+}
+
+// Sum the power generated and drawn by the working parts of every ship, including its sections
+fn power_grid_system(
+    mut ships: Query<(Entity, &mut PowerGrid)>,
+    sections: Query<&ShipSection>,
+    reactors: Query<(&ShipReactor, &Parent)>,
+    engines: Query<(&ShipEngine, &Heat, &Parent)>,
+    weapons: Query<(&ShipWeapon, &Heat, &Parent)>,
+) {
+    let ship_of = |parent: &Parent| {
+        sections
+            .get(parent.get())
+            .map_or(parent.get(), |section| section.ship)
+    };
+    for (_, mut grid) in ships.iter_mut() {
+        grid.supply = 0.0;
+        grid.engines = 0.0;
+        grid.weapons = 0.0;
+    }
+    for (reactor, parent) in reactors.iter() {
+        if let Ok((_, mut grid)) = ships.get_mut(ship_of(parent)) {
+            grid.supply += reactor.output;
+        }
+    }
+    // Overheated parts are shut down
+    for (engine, _, parent) in engines.iter().filter(|(_, heat, _)| !heat.overheated) {
+        if let Ok((_, mut grid)) = ships.get_mut(ship_of(parent)) {
+            grid.engines += engine.power;
+        }
+    }
+    for (weapon, _, parent) in weapons.iter().filter(|(_, heat, _)| !heat.overheated) {
+        if let Ok((_, mut grid)) = ships.get_mut(ship_of(parent)) {
+            grid.weapons += weapon.power;
+        }
+    }
+}
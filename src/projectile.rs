@@ -0,0 +1,304 @@
+use bevy::utils::HashSet;
+
+use crate::prelude::*;
+
+/// Sent when a projectile hits something and must be removed
+pub struct ProjectileDespawnEvent(pub Entity);
+/// Sent when a weapon mount fires a shot
+pub struct ProjectileSpawnEvent {
+    pub weapon: WeaponKind,
+    // The full position (translation+rotation) of the muzzle
+    pub transform: Transform,
+    // The velocity of the muzzle, including the rotation of the ship it is mounted on
+    pub velocity: Vec2,
+    // The ship emitting the projectiles
+    pub shooter: Entity,
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    pub despawn_timer: Timer,
+    pub shooter: Entity,
+}
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ProjectileDespawnEvent>()
+            .add_event::<ProjectileSpawnEvent>()
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    .with_system(projectile_timeout_system)
+                    .with_system(projectile_despawn_system.after(ContactResolution))
+                    .with_system(spawn_projectile),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, projectile_timeout_system);
+    }
+}
+
+// Every shot fires the burst of its weapon, scattered within its spread
+fn spawn_projectile(
+    mut commands: Commands,
+    mut projectile_spawn_events: EventReader<ProjectileSpawnEvent>,
+    handles: Res<SpriteAssets>,
+    audios: Res<AudioAssets>,
+    audio_output: Res<Audio>,
+) {
+    let mut rng = thread_rng();
+    for spawn_event in projectile_spawn_events.iter() {
+        let definition = spawn_event.weapon.definition();
+        for _ in 0..definition.burst {
+            let deviation = if definition.spread > 0.0 {
+                rng.gen_range(-definition.spread / 2.0..definition.spread / 2.0)
+            } else {
+                0.0
+            };
+            let rotation = spawn_event.transform.rotation * Quat::from_rotation_z(deviation);
+            let translation = spawn_event.transform.translation;
+            let mut projectile = commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(definition.size),
+                        color: definition.color,
+                        ..Default::default()
+                    },
+                    transform: Transform {
+                        translation: Vec3::new(translation.x, translation.y, 2.0),
+                        rotation,
+                        ..Default::default()
+                    },
+                    texture: handles.projectile(definition.sprite),
+                    ..Default::default()
+                },
+                Projectile {
+                    despawn_timer: Timer::from_seconds(definition.lifetime, TimerMode::Once),
+                    shooter: spawn_event.shooter,
+                },
+                Damage {
+                    value: definition.damage,
+                },
+                ForState {
+                    states: vec![AppState::Game],
+                },
+                projectile_body(
+                    definition,
+                    projectile_velocity(spawn_event.velocity, rotation, definition.speed),
+                ),
+            ));
+            if definition.missile.is_some() {
+                projectile.insert(Missile {
+                    kind: spawn_event.weapon,
+                });
+            }
+        }
+        audio_output.play(audios.weapon(definition.sound));
+    }
+}
+
+/// Velocity of a projectile fired at a speed from a moving muzzle
+pub fn projectile_velocity(muzzle_velocity: Vec2, rotation: Quat, speed: f32) -> Vec2 {
+    muzzle_velocity + (rotation * Vec3::Y * speed).truncate()
+}
+
+// Fast projectiles would tunnel through small parts between two steps without CCD
+fn projectile_body(definition: &WeaponDefinition, linvel: Vec2) -> impl Bundle {
+    (
+        RigidBody::Dynamic,
+        Collider::cuboid(definition.size.x / 2.0, definition.size.y / 2.0),
+        Velocity::linear(linvel),
+        Ccd::enabled(),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+    )
+}
+
+// Missiles blow up when removed, whether they hit something or ran out of time
+fn detonation(
+    missile: &Missile,
+    projectile: &Projectile,
+    transform: &Transform,
+) -> DetonationEvent {
+    let guidance = missile.kind.definition().missile.as_ref();
+    DetonationEvent {
+        position: transform.translation.truncate(),
+        radius: guidance.map_or(0.0, |guidance| guidance.blast_radius),
+        damage: guidance.map_or(0, |guidance| guidance.blast_damage),
+        shooter: projectile.shooter,
+    }
+}
+
+fn projectile_timeout_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    gamestate: Res<State<AppGameState>>,
+    mut detonation_events: EventWriter<DetonationEvent>,
+    mut query: Query<(Entity, &mut Projectile, &Transform, Option<&Missile>)>,
+) {
+    if gamestate.current() == &AppGameState::Game {
+        for (entity, mut projectile, transform, missile) in query.iter_mut() {
+            projectile.despawn_timer.tick(time.delta());
+            if projectile.despawn_timer.finished() {
+                if let Some(missile) = missile {
+                    detonation_events.send(detonation(missile, &projectile, transform));
+                }
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+fn projectile_despawn_system(
+    mut commands: Commands,
+    mut projectile_despawn_events: EventReader<ProjectileDespawnEvent>,
+    mut detonation_events: EventWriter<DetonationEvent>,
+    projectiles: Query<(Entity, &Projectile, &Transform, Option<&Missile>)>,
+) {
+    let despawned: HashSet<Entity> = projectile_despawn_events
+        .iter()
+        .map(|event| event.0)
+        .collect();
+    for (entity, projectile, transform, missile) in projectiles.iter_many(despawned) {
+        if let Some(missile) = missile {
+            detonation_events.send(detonation(missile, projectile, transform));
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy::time::TimePlugin;
+
+    use super::*;
+
+    const STEP: f32 = 1.0 / 60.0;
+
+    // Headless physics world stepping exactly once per update
+    fn physics_app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin::default())
+            .add_plugin(TimePlugin)
+            .add_plugin(TransformPlugin)
+            .add_plugin(HierarchyPlugin)
+            .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
+            .insert_resource(RapierConfiguration {
+                gravity: Vec2::ZERO,
+                timestep_mode: TimestepMode::Fixed {
+                    dt: STEP,
+                    substeps: 1,
+                },
+                ..Default::default()
+            });
+        app
+    }
+
+    fn spawn_target(app: &mut App, position: Vec2, linvel: Vec2) -> Entity {
+        app.world
+            .spawn((
+                TransformBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+                RigidBody::Dynamic,
+                Collider::ball(ENGINE_RADIUS),
+                Velocity::linear(linvel),
+            ))
+            .id()
+    }
+
+    fn spawn_shot(app: &mut App, weapon: WeaponKind, position: Vec2, linvel: Vec2) -> Entity {
+        app.world
+            .spawn((
+                TransformBundle::from_transform(Transform::from_translation(position.extend(0.0))),
+                projectile_body(weapon.definition(), linvel),
+            ))
+            .id()
+    }
+
+    // Whether the shot reported touching the target within the given steps
+    fn hits(app: &mut App, shot: Entity, target: Entity, steps: usize) -> bool {
+        for _ in 0..steps {
+            app.update();
+            let events = app.world.resource::<Events<CollisionEvent>>();
+            let hit = events.iter_current_update_events().any(|event| {
+                matches!(event, CollisionEvent::Started(a, b, _)
+                    if (*a, *b) == (shot, target) || (*a, *b) == (target, shot))
+            });
+            if hit {
+                return true;
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn projectile_inherits_sideways_velocity() {
+        let velocity = projectile_velocity(Vec2::new(300.0, -50.0), Quat::IDENTITY, 500.0);
+        assert_eq!(velocity, Vec2::new(300.0, 450.0));
+    }
+
+    #[test]
+    fn projectile_fires_along_the_muzzle() {
+        let rotation = Quat::from_rotation_z(FRAC_PI_2);
+        let velocity = projectile_velocity(Vec2::ZERO, rotation, 500.0);
+        assert!((velocity - Vec2::new(-500.0, 0.0)).length() < 1e-3);
+    }
+
+    #[test]
+    fn muzzle_velocity_includes_spin() {
+        let mut app = physics_app();
+        let body = app
+            .world
+            .spawn((
+                TransformBundle::default(),
+                RigidBody::Dynamic,
+                Collider::ball(10.0),
+                Velocity {
+                    linvel: Vec2::new(100.0, 0.0),
+                    angvel: 2.0,
+                },
+            ))
+            .id();
+        app.update();
+        let handle = app.world.get::<RapierRigidBodyHandle>(body).unwrap().0;
+        let center = app
+            .world
+            .get::<Transform>(body)
+            .unwrap()
+            .translation
+            .truncate();
+        let rapier_context = app.world.resource::<RapierContext>();
+        let velocity =
+            body_velocity_at_point(rapier_context, handle, center + Vec2::new(0.0, 50.0)).unwrap();
+        // The spin pushes a mount above the center backward
+        assert!(velocity.length() < 1.0, "{velocity}");
+    }
+
+    #[test]
+    fn fast_shot_hits_small_part() {
+        let mut app = physics_app();
+        let target = spawn_target(&mut app, Vec2::new(0.0, 125.0), Vec2::ZERO);
+        // A single step covers several times the size of the target
+        let shot = spawn_shot(
+            &mut app,
+            WeaponKind::Sniper,
+            Vec2::ZERO,
+            Vec2::new(0.0, 3000.0),
+        );
+        assert!(hits(&mut app, shot, target, 10));
+    }
+
+    #[test]
+    fn shot_from_strafing_ship_hits_closing_target() {
+        let mut app = physics_app();
+        let shooter_velocity = Vec2::new(800.0, 0.0);
+        // Both meet half a second later, only if the shot keeps the sideways motion of the ship
+        let target = spawn_target(&mut app, Vec2::new(400.0, 1000.0), Vec2::new(0.0, -1500.0));
+        let shot = spawn_shot(
+            &mut app,
+            WeaponKind::Laser,
+            Vec2::ZERO,
+            projectile_velocity(shooter_velocity, Quat::IDENTITY, LASER.speed),
+        );
+        assert!(hits(&mut app, shot, target, 30));
+    }
+}
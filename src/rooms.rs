@@ -0,0 +1,279 @@
+use bevy::utils::{HashMap, HashSet};
+
+use crate::prelude::*;
+
+/// Closed polygon of walls inside a ship, in ship local coordinates
+#[derive(Component, Debug)]
+pub struct Room {
+    pub polygon: Vec<Vec2>,
+    pub area: f32,
+    pub perimeter: f32,
+}
+
+impl Room {
+    /// Whether a point, in ship local coordinates, is inside the room
+    pub fn contains(&self, point: Vec2) -> bool {
+        let mut inside = false;
+        for (a, b) in self.polygon.iter().zip(self.polygon.iter().cycle().skip(1)) {
+            if (a.y > point.y) != (b.y > point.y)
+                && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// Distance from a point to the closest wall of the room
+    pub fn distance_to_walls(&self, point: Vec2) -> f32 {
+        self.polygon
+            .iter()
+            .zip(self.polygon.iter().cycle().skip(1))
+            .map(|(a, b)| point_segment_distance(point, *a, *b))
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// Room area px2 used by the modules inside it
+    pub fn used_area<'a>(&self, parts: impl IntoIterator<Item = &'a ShipPart>) -> f32 {
+        parts
+            .into_iter()
+            .filter_map(|part| match *part {
+                ShipPart::Module { kind, position } if self.contains(position) => {
+                    Some(kind.footprint())
+                }
+                _ => None,
+            })
+            .sum()
+    }
+}
+
+pub struct RoomsPlugin;
+
+impl Plugin for RoomsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_update(AppState::Game).with_system(room_detection_system));
+    }
+}
+
+// Look for rooms again whenever walls are built, destroyed, or sections are lost
+fn room_detection_system(
+    mut commands: Commands,
+    ships: Query<(Entity, &Children, ChangeTrackers<Children>), With<Ship>>,
+    sections: Query<(&ShipSection, &Children, ChangeTrackers<Children>)>,
+    removed_sections: RemovedComponents<ShipSection>,
+    parts: Query<&ShipPart>,
+    rooms: Query<(Entity, &Room, &Parent)>,
+) {
+    let any_section_removed = removed_sections.iter().next().is_some();
+    for (ship_entity, children, children_tracker) in ships.iter() {
+        let ship_sections: Vec<_> = sections
+            .iter()
+            .filter(|(section, _, _)| section.ship == ship_entity)
+            .collect();
+        if !any_section_removed
+            && !children_tracker.is_changed()
+            && !ship_sections
+                .iter()
+                .any(|(_, _, children_tracker)| children_tracker.is_changed())
+        {
+            continue;
+        }
+        let walls: Vec<(Vec2, Vec2)> = parts
+            .iter_many(
+                children.iter().chain(
+                    ship_sections
+                        .iter()
+                        .flat_map(|(_, children, _)| children.iter()),
+                ),
+            )
+            .filter_map(|part| match *part {
+                ShipPart::Wall { start, end } => Some((start, end)),
+                _ => None,
+            })
+            .collect();
+        let polygons = find_rooms(&walls);
+
+        // Rooms are children of the ship too, only replace them when they actually changed
+        let ship_rooms: Vec<_> = rooms
+            .iter()
+            .filter(|(_, _, parent)| parent.get() == ship_entity)
+            .collect();
+        let unchanged = ship_rooms.len() == polygons.len()
+            && polygons.iter().all(|polygon| {
+                ship_rooms
+                    .iter()
+                    .any(|(_, room, _)| same_polygon(&room.polygon, polygon))
+            });
+        if unchanged {
+            continue;
+        }
+        for (entity, _, _) in ship_rooms {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.entity(ship_entity).with_children(|parent| {
+            for polygon in polygons {
+                let shape = shapes::Polygon {
+                    points: polygon.clone(),
+                    closed: true,
+                };
+                parent.spawn((
+                    Room {
+                        area: polygon_area(&polygon),
+                        perimeter: polygon_perimeter(&polygon),
+                        polygon,
+                    },
+                    GeometryBuilder::build_as(
+                        &shape,
+                        DrawMode::Fill(FillMode::color(Color::rgba(0.3, 0.5, 0.9, 0.25))),
+                        Transform::from_xyz(0.0, 0.0, 0.05),
+                    ),
+                ));
+            }
+        });
+    }
+}
+
+fn same_polygon(a: &[Vec2], b: &[Vec2]) -> bool {
+    a.len() == b.len() && a.iter().all(|point| b.contains(point))
+}
+
+/// Signed area, positive for counter-clockwise polygons
+pub fn polygon_area(polygon: &[Vec2]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f32>()
+        / 2.0
+}
+
+pub fn polygon_perimeter(polygon: &[Vec2]) -> f32 {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(a, b)| a.distance(*b))
+        .sum()
+}
+
+// Points are snapped to 1/100th of a pixel, so that crossings computed from different walls match
+fn point_key(point: Vec2) -> (i64, i64) {
+    (
+        (point.x * 100.0).round() as i64,
+        (point.y * 100.0).round() as i64,
+    )
+}
+
+/// Find every closed polygon formed by the walls, as counter-clockwise lists of points.
+/// Walls are split where they cross or touch each other, then each face of the resulting
+/// planar graph is traced, always taking the leftmost turn.
+pub fn find_rooms(walls: &[(Vec2, Vec2)]) -> Vec<Vec<Vec2>> {
+    // Split every wall at each point it meets another wall
+    let mut points: HashMap<(i64, i64), Vec2> = HashMap::default();
+    let mut edges: HashSet<((i64, i64), (i64, i64))> = HashSet::default();
+    for (index, (start, end)) in walls.iter().enumerate() {
+        let direction = *end - *start;
+        if direction.length_squared() == 0.0 {
+            continue;
+        }
+        let mut splits = vec![0.0, 1.0];
+        for (other_index, (other_start, other_end)) in walls.iter().enumerate() {
+            if index == other_index {
+                continue;
+            }
+            // Other wall ends touching this one, also covers overlapping walls
+            for point in [*other_start, *other_end] {
+                if point_segment_distance(point, *start, *end) < 0.01 {
+                    splits.push((point - *start).dot(direction) / direction.length_squared());
+                }
+            }
+            // Walls crossing each other
+            let other_direction = *other_end - *other_start;
+            let denominator = direction.perp_dot(other_direction);
+            if denominator.abs() > f32::EPSILON {
+                let offset = *other_start - *start;
+                let t = offset.perp_dot(other_direction) / denominator;
+                let u = offset.perp_dot(direction) / denominator;
+                if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+                    splits.push(t);
+                }
+            }
+        }
+        splits.sort_by(|a, b| a.total_cmp(b));
+        let keys: Vec<_> = splits
+            .iter()
+            .map(|t| {
+                let key = point_key(*start + direction * t.clamp(0.0, 1.0));
+                points.insert(key, Vec2::new(key.0 as f32, key.1 as f32) / 100.0);
+                key
+            })
+            .collect();
+        for (a, b) in keys.iter().zip(keys.iter().skip(1)) {
+            if a != b {
+                edges.insert((*a.min(b), *a.max(b)));
+            }
+        }
+    }
+
+    let mut neighbors: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::default();
+    for (a, b) in edges.iter() {
+        neighbors.entry(*a).or_default().push(*b);
+        neighbors.entry(*b).or_default().push(*a);
+    }
+    // Dangling walls can not be part of any room
+    let mut dangling: Vec<_> = neighbors
+        .iter()
+        .filter(|(_, others)| others.len() < 2)
+        .map(|(point, _)| *point)
+        .collect();
+    while let Some(point) = dangling.pop() {
+        if let Some(others) = neighbors.remove(&point) {
+            for other in others {
+                if let Some(other_neighbors) = neighbors.get_mut(&other) {
+                    other_neighbors.retain(|neighbor| *neighbor != point);
+                    if other_neighbors.len() < 2 {
+                        dangling.push(other);
+                    }
+                }
+            }
+        }
+    }
+    // Sort neighbors counter-clockwise around each point
+    for (point, others) in neighbors.iter_mut() {
+        let origin = points[point];
+        others.sort_by(|a, b| {
+            let angle = |key: &(i64, i64)| {
+                let direction = points[key] - origin;
+                direction.y.atan2(direction.x)
+            };
+            angle(a).total_cmp(&angle(b))
+        });
+    }
+
+    // Trace the face on the left of every half edge.
+    // Rooms are traced counter-clockwise, the outside of the ship is traced clockwise.
+    let mut visited = HashSet::default();
+    let mut rooms = Vec::new();
+    let mut half_edges: Vec<_> = neighbors
+        .iter()
+        .flat_map(|(point, others)| others.iter().map(|other| (*point, *other)))
+        .collect();
+    half_edges.sort();
+    for half_edge in half_edges {
+        if visited.contains(&half_edge) {
+            continue;
+        }
+        let mut polygon = Vec::new();
+        let (mut from, mut to) = half_edge;
+        while visited.insert((from, to)) {
+            polygon.push(points[&from]);
+            let others = &neighbors[&to];
+            let back = others.iter().position(|other| *other == from).unwrap();
+            let next = others[(back + others.len() - 1) % others.len()];
+            (from, to) = (to, next);
+        }
+        if polygon_area(&polygon) > 1.0 {
+            rooms.push(polygon);
+        }
+    }
+    rooms
+}
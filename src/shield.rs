@@ -0,0 +1,189 @@
+use crate::prelude::*;
+
+/// Damage absorbed by the shield of a single generator
+pub const SHIELD_CAPACITY: f32 = 6.0;
+/// Strength regained per second by a single generator
+pub const SHIELD_RECHARGE: f32 = 1.0;
+/// Power W drawn by a single generator while recharging
+pub const SHIELD_POWER: f32 = 15.0;
+/// Gap px between the farthest part of a ship and its bubble
+pub const SHIELD_MARGIN: f32 = 12.0;
+/// Opacity of the bubble of a full shield
+pub const SHIELD_ALPHA: f32 = 0.35;
+
+/// Shield of a ship, supplied by its generators.
+/// Damage dealt to the ship drains it before reaching the parts.
+#[derive(Component, Debug, Default)]
+pub struct Shield {
+    pub strength: f32,
+    /// Strength of a full shield
+    pub capacity: f32,
+    /// Strength regained per second
+    pub recharge: f32,
+    /// Power W drawn while recharging
+    pub power: f32,
+}
+
+impl Shield {
+    /// Drain the shield, returning the damage it could not absorb.
+    /// Any fraction of a point left over still hits as a whole point.
+    pub fn absorb(&mut self, damage: Damage) -> Damage {
+        let absorbed = self.strength.min(damage.value as f32);
+        self.strength -= absorbed;
+        Damage {
+            value: (damage.value as f32 - absorbed).ceil() as u32,
+        }
+    }
+}
+
+/// Sensor bubble around a ship, stopping incoming projectiles while its shield holds
+#[derive(Component)]
+pub struct ShieldBubble {
+    pub ship: Entity,
+    pub radius: f32,
+}
+
+pub struct ShieldPlugin;
+
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(shield_gauge_system)
+                .with_system(shield_recharge_system.after(shield_gauge_system))
+                .with_system(shield_bubble_system.after(shield_recharge_system)),
+        );
+    }
+}
+
+// Sum the generators of every ship, including those of its sections
+fn shield_gauge_system(
+    mut ships: Query<(Entity, &mut Shield)>,
+    sections: Query<&ShipSection>,
+    generators: Query<(&ShipShield, &Heat, &Parent)>,
+) {
+    let ship_of = |parent: &Parent| {
+        sections
+            .get(parent.get())
+            .map_or(parent.get(), |section| section.ship)
+    };
+    for (_, mut shield) in ships.iter_mut() {
+        shield.capacity = 0.0;
+        shield.recharge = 0.0;
+        shield.power = 0.0;
+    }
+    // Overheated generators are shut down
+    for (generator, _, parent) in generators.iter().filter(|(_, heat, _)| !heat.overheated) {
+        if let Ok((_, mut shield)) = ships.get_mut(ship_of(parent)) {
+            shield.capacity += generator.capacity;
+            shield.recharge += generator.recharge;
+            shield.power += generator.power;
+        }
+    }
+    for (_, mut shield) in ships.iter_mut() {
+        shield.strength = shield.strength.min(shield.capacity);
+    }
+}
+
+// Generators draw power until their shield is full, recharging slower during brownouts
+fn shield_recharge_system(time: Res<Time>, mut ships: Query<(&mut Shield, &mut PowerGrid)>) {
+    for (mut shield, mut grid) in ships.iter_mut() {
+        if shield.strength < shield.capacity {
+            grid.shields = shield.power;
+            shield.strength = (shield.strength
+                + shield.recharge * grid.satisfaction() * time.delta_seconds())
+            .min(shield.capacity);
+        } else {
+            grid.shields = 0.0;
+        }
+    }
+}
+
+// Wrap every shielded ship in a bubble fitting its parts, fading as the shield weakens.
+// Sections share the ship local coordinates, their parts are wrapped as well.
+fn shield_bubble_system(
+    mut commands: Commands,
+    ships: Query<(Entity, &Shield, &Children)>,
+    sections: Query<(&ShipSection, &Children)>,
+    parts: Query<&ShipPart>,
+    mut bubbles: Query<(
+        Entity,
+        &mut ShieldBubble,
+        &mut Collider,
+        &mut Path,
+        &mut DrawMode,
+    )>,
+) {
+    // The bubble of a lost ship bursts
+    for (entity, bubble, _, _, _) in bubbles.iter() {
+        if !ships.contains(bubble.ship) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    for (ship_entity, shield, children) in ships.iter() {
+        let bubble = bubbles
+            .iter_mut()
+            .find(|(_, bubble, _, _, _)| bubble.ship == ship_entity);
+        if shield.capacity <= 0.0 {
+            if let Some((entity, _, _, _, _)) = bubble {
+                commands.entity(entity).despawn_recursive();
+            }
+            continue;
+        }
+        let section_children = sections
+            .iter()
+            .filter(|(section, _)| section.ship == ship_entity)
+            .flat_map(|(_, children)| children.iter());
+        let extent = parts
+            .iter_many(children.iter().chain(section_children))
+            .map(|part| match *part {
+                ShipPart::Wall { start, end } => start.length().max(end.length()),
+                part => part.center().length(),
+            })
+            .fold(0.0, f32::max);
+        let radius = extent + SHIELD_MARGIN;
+        let circle = shapes::Circle {
+            radius,
+            center: Vec2::ZERO,
+        };
+        let color = Color::rgba(
+            0.4,
+            0.7,
+            1.0,
+            SHIELD_ALPHA * shield.strength / shield.capacity,
+        );
+        let Some((_, mut bubble, mut collider, mut path, mut draw_mode)) = bubble else {
+            commands.entity(ship_entity).with_children(|parent| {
+                parent.spawn((
+                    ShieldBubble {
+                        ship: ship_entity,
+                        radius,
+                    },
+                    GeometryBuilder::build_as(
+                        &circle,
+                        DrawMode::Outlined {
+                            fill_mode: FillMode::color(color),
+                            outline_mode: StrokeMode::new(color, 2.0),
+                        },
+                        Transform::from_xyz(0.0, 0.0, 0.5),
+                    ),
+                    Collider::ball(radius),
+                    // The bubble does not weigh on its ship
+                    ColliderMassProperties::Density(0.0),
+                    Sensor,
+                    ActiveEvents::COLLISION_EVENTS,
+                ));
+            });
+            continue;
+        };
+        if (bubble.radius - radius).abs() > 1.0 {
+            bubble.radius = radius;
+            *collider = Collider::ball(radius);
+            *path = ShapePath::build_as(&circle);
+        }
+        *draw_mode = DrawMode::Outlined {
+            fill_mode: FillMode::color(color),
+            outline_mode: StrokeMode::new(color, 2.0),
+        };
+    }
+}
@@ -36,6 +36,13 @@ pub enum BuildState {
     None,
     WallTool,
     EngineTool,
+    WeaponTool,
+    RadiatorTool,
+    CollectorTool,
+    TractorTool,
+    ShieldTool,
+    TurretTool,
+    ModuleTool,
 }
 
 pub struct StatesPlugin;
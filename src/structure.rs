@@ -0,0 +1,304 @@
+use bevy::utils::{HashMap, HashSet};
+use bevy_rapier2d::rapier::geometry::SolverFlags;
+
+use crate::prelude::*;
+
+/// Size of the square areas a ship design is cut into, each one becoming its own rigid body
+pub const SECTION_SIZE: f32 = 120.0;
+/// Force N a single wall can hold between two sections before their joint breaks
+pub const WALL_JOINT_STRENGTH: f32 = 1500.0;
+
+/// Rigid body holding the parts of one area of a large ship.
+/// Sections share the ship local coordinates, so parts keep their design position.
+#[derive(Component)]
+pub struct ShipSection {
+    /// The ship root body, holding the core section
+    pub ship: Entity,
+    /// Thrust N of the engines of this section
+    pub thrust: f32,
+}
+
+/// Fixed joint between two sections of a ship
+#[derive(Component)]
+pub struct StructuralJoint {
+    /// Force N above which the joint breaks, depends on the number of walls joining the sections
+    pub strength: f32,
+}
+
+/// Physics hook keeping the sections of a ship from colliding with each other.
+/// Only jointed pairs have their contacts disabled by the joint itself,
+/// sections meeting at a corner or through another section would still collide.
+pub struct SectionContactFilter;
+
+impl PhysicsHooksWithQuery<&ShipSection> for SectionContactFilter {
+    fn filter_contact_pair(
+        &self,
+        context: PairFilterContextView,
+        sections: &Query<&ShipSection>,
+    ) -> Option<SolverFlags> {
+        let ship_of = |body: Entity| sections.get(body).map_or(body, |section| section.ship);
+        match (context.rigid_body1(), context.rigid_body2()) {
+            (Some(body1), Some(body2)) if ship_of(body1) == ship_of(body2) => None,
+            _ => Some(SolverFlags::COMPUTE_IMPULSES),
+        }
+    }
+}
+
+/// Tag component for a ship whose parts all belong to its root body.
+/// It is cut into sections on the next frame.
+#[derive(Component)]
+pub struct Unassembled;
+
+pub struct StructurePlugin;
+
+impl Plugin for StructurePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(ship_assembly_system)
+                .with_system(section_engines_system)
+                .with_system(joint_breaking_system)
+                .with_system(section_cleanup_system)
+                .with_system(detached_sections_system),
+        )
+        .add_system_set(
+            SystemSet::on_enter(PlayerState::Building).with_system(ship_disassembly_system),
+        )
+        .add_system_set(
+            SystemSet::on_exit(PlayerState::Building).with_system(ship_reassembly_system),
+        );
+    }
+}
+
+fn section_cell(part: &ShipPart) -> IVec2 {
+    (part.center() / SECTION_SIZE).round().as_ivec2()
+}
+
+// Cut the ship into sections, jointed together where walls join them
+fn ship_assembly_system(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    ships: Query<
+        (
+            Entity,
+            &Transform,
+            &Velocity,
+            &RapierRigidBodyHandle,
+            &Children,
+        ),
+        With<Unassembled>,
+    >,
+    parts: Query<(Entity, &ShipPart, PartState)>,
+) {
+    for (ship_entity, transform, velocity, body_handle, children) in ships.iter() {
+        commands.entity(ship_entity).remove::<Unassembled>();
+        let ship_parts: Vec<_> = parts.iter_many(children).collect();
+        let designs: Vec<ShipPart> = ship_parts.iter().map(|(_, part, _)| **part).collect();
+        let core_cell = match core_part(&designs) {
+            Some(core) => section_cell(&designs[core]),
+            None => continue,
+        };
+        let mut cells: HashMap<IVec2, Vec<usize>> = HashMap::default();
+        for (index, part) in designs.iter().enumerate() {
+            cells.entry(section_cell(part)).or_default().push(index);
+        }
+
+        // The core section stays on the ship root body
+        let mut bodies = HashMap::default();
+        for (cell, indices) in cells.iter() {
+            if *cell == core_cell {
+                bodies.insert(*cell, ship_entity);
+                continue;
+            }
+            let centroid = indices
+                .iter()
+                .map(|index| designs[*index].center())
+                .sum::<Vec2>()
+                / indices.len() as f32;
+            let point = transform.transform_point(centroid.extend(0.0)).truncate();
+            let linvel = body_velocity_at_point(&rapier_context, body_handle.0, point)
+                .unwrap_or(velocity.linvel);
+            let section = commands
+                .spawn((
+                    ShipSection {
+                        ship: ship_entity,
+                        thrust: 0.0,
+                    },
+                    ForState {
+                        states: vec![AppState::Game],
+                    },
+                    RigidBody::Dynamic,
+                    ExternalImpulse::default(),
+                    Velocity {
+                        linvel,
+                        angvel: velocity.angvel,
+                    },
+                    GeometryBuilder::build_as(
+                        &PathBuilder::new().build(),
+                        DrawMode::Stroke(StrokeMode::new(Color::WHITE, WALL_WIDTH)),
+                        *transform,
+                    ),
+                ))
+                .with_children(|parent| {
+                    for index in indices {
+                        let (_, part, state) = &ship_parts[*index];
+                        part.respawn(parent, state);
+                    }
+                })
+                .id();
+            for index in indices {
+                commands.entity(ship_parts[*index].0).despawn_recursive();
+            }
+            bodies.insert(*cell, section);
+        }
+
+        // Every wall attached across two sections strengthens their joint
+        let mut links: HashMap<(IVec2, IVec2), Vec<Vec2>> = HashMap::default();
+        for (index, part) in designs.iter().enumerate() {
+            for other in designs.iter().skip(index + 1) {
+                let (cell, other_cell) = (section_cell(part), section_cell(other));
+                let is_wall =
+                    matches!(part, ShipPart::Wall { .. }) || matches!(other, ShipPart::Wall { .. });
+                if cell != other_cell && is_wall && part.is_attached_to(other) {
+                    let key = if cell.to_array() < other_cell.to_array() {
+                        (cell, other_cell)
+                    } else {
+                        (other_cell, cell)
+                    };
+                    links
+                        .entry(key)
+                        .or_default()
+                        .push((part.center() + other.center()) / 2.0);
+                }
+            }
+        }
+        for ((cell1, cell2), anchors) in links {
+            // The joint is added as a child of its second body, keep the ship root first
+            let (body1, body2) = match (bodies[&cell1], bodies[&cell2]) {
+                (body1, body2) if body2 == ship_entity => (body2, body1),
+                bodies => bodies,
+            };
+            // All sections share the same local coordinates
+            let anchor = anchors.iter().sum::<Vec2>() / anchors.len() as f32;
+            // Parts of jointed sections touch each other, they must not collide
+            let mut joint = FixedJointBuilder::new()
+                .local_anchor1(anchor)
+                .local_anchor2(anchor)
+                .build();
+            joint.set_contacts_enabled(false);
+            commands.entity(body2).with_children(|parent| {
+                parent.spawn((
+                    StructuralJoint {
+                        strength: anchors.len() as f32 * WALL_JOINT_STRENGTH,
+                    },
+                    ImpulseJoint::new(body1, joint),
+                ));
+            });
+        }
+    }
+}
+
+// Recompute the section thrust as its engines are destroyed, or overheat
+fn section_engines_system(
+    mut sections: Query<(&mut ShipSection, &Children)>,
+    engines: Query<(&ShipEngine, &Heat)>,
+) {
+    for (mut section, children) in sections.iter_mut() {
+        section.thrust = engines
+            .iter_many(children)
+            .filter(|(_, heat)| !heat.overheated)
+            .map(|(engine, _)| engine.thrust)
+            .sum();
+    }
+}
+
+fn joint_breaking_system(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    joints: Query<(Entity, &StructuralJoint, &RapierImpulseJointHandle)>,
+) {
+    let scale = rapier_context.physics_scale();
+    let dt = rapier_context.integration_parameters.dt;
+    for (entity, joint, handle) in joints.iter() {
+        if let Some(impulse_joint) = rapier_context.impulse_joints.get(handle.0) {
+            let impulse = Vec2::new(impulse_joint.impulses.x, impulse_joint.impulses.y) * scale;
+            if impulse.length() / dt > joint.strength {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+// Remove sections once all their parts are destroyed, or split off as debris
+fn section_cleanup_system(
+    mut commands: Commands,
+    sections: Query<(Entity, Option<&Children>), With<ShipSection>>,
+    parts: Query<&ShipPart>,
+) {
+    for (entity, children) in sections.iter() {
+        if children.is_none_or(|children| parts.iter_many(children).next().is_none()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// Sections no longer jointed to their ship, directly or through other sections, become debris
+fn detached_sections_system(
+    mut commands: Commands,
+    sections: Query<(Entity, &ShipSection)>,
+    joints: Query<(&ImpulseJoint, &Parent), With<StructuralJoint>>,
+) {
+    let mut links: HashMap<Entity, Vec<Entity>> = HashMap::default();
+    for (joint, parent) in joints.iter() {
+        links.entry(joint.parent).or_default().push(parent.get());
+        links.entry(parent.get()).or_default().push(joint.parent);
+    }
+    let mut attached: HashSet<Entity> = sections.iter().map(|(_, section)| section.ship).collect();
+    let mut queue: Vec<Entity> = attached.iter().copied().collect();
+    while let Some(body) = queue.pop() {
+        for other in links.get(&body).into_iter().flatten() {
+            if attached.insert(*other) {
+                queue.push(*other);
+            }
+        }
+    }
+    for (entity, _) in sections.iter() {
+        if !attached.contains(&entity) {
+            commands
+                .entity(entity)
+                .remove::<ShipSection>()
+                .remove::<ExternalImpulse>()
+                .insert(Debris);
+        }
+    }
+}
+
+// While building, the whole design is edited on the ship root body.
+// Enemy ships keep flying in sections.
+fn ship_disassembly_system(
+    mut commands: Commands,
+    sections: Query<(Entity, &ShipSection, &Children)>,
+    parts: Query<(&ShipPart, PartState)>,
+    enemies: Query<(), With<Enemy>>,
+) {
+    for (entity, section, children) in sections.iter() {
+        if enemies.contains(section.ship) {
+            continue;
+        }
+        commands.entity(section.ship).with_children(|parent| {
+            for (part, state) in parts.iter_many(children) {
+                part.respawn(parent, &state);
+            }
+        });
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn ship_reassembly_system(
+    mut commands: Commands,
+    ships: Query<Entity, (With<Ship>, Without<Enemy>)>,
+) {
+    for entity in ships.iter() {
+        commands.entity(entity).insert(Unassembled);
+    }
+}
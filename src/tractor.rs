@@ -0,0 +1,182 @@
+use bevy_rapier2d::rapier::prelude::{JointAxesMask, JointAxis, MotorModel};
+
+use crate::prelude::*;
+
+/// Reach px of the tractor beams
+pub const TRACTOR_RANGE: f32 = 200.0;
+/// Closest distance px ahead of the mount a towed body is held at
+pub const TRACTOR_MIN_HOLD: f32 = 40.0;
+/// Spring stiffness N/m pulling the towed body to its hold point
+pub const TRACTOR_STIFFNESS: f32 = 40.0;
+pub const TRACTOR_DAMPING: f32 = 8.0;
+
+/// Asteroid, debris or ore pickup held by a tractor beam mount
+#[derive(Component)]
+pub struct Towed {
+    pub tractor: Entity,
+}
+
+/// Lines of every tractor beam in use
+#[derive(Component)]
+struct TractorBeams;
+
+pub struct TractorPlugin;
+
+impl Plugin for TractorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(AppState::Game).with_system(tractor_setup))
+            .add_system_set(
+                SystemSet::on_update(AppState::Game)
+                    // Before any towable body is despawned by the contacts
+                    .with_system(tractor_system.before(ContactResolution))
+                    .with_system(tractor_leash_system)
+                    .with_system(tractor_beams_system),
+            )
+            .add_system_set(
+                SystemSet::on_enter(PlayerState::Building).with_system(tractor_release_system),
+            );
+    }
+}
+
+fn tractor_setup(mut commands: Commands) {
+    commands.spawn((
+        TractorBeams,
+        GeometryBuilder::build_as(
+            &ShapePath::new().build(),
+            DrawMode::Stroke(StrokeMode::new(Color::rgba(0.6, 0.4, 1.0, 0.6), 2.0)),
+            Transform::from_xyz(0.0, 0.0, 1.8),
+        ),
+        ForState {
+            states: vec![AppState::Game],
+        },
+    ));
+}
+
+// Toggle the tractor beams: each mount either releases its load,
+// or latches on the first towable body in front of it with a spring.
+fn tractor_system(
+    mut commands: Commands,
+    gamestate: Res<State<AppGameState>>,
+    playerstate: Res<State<PlayerState>>,
+    rapier_context: Res<RapierContext>,
+    ships: Query<&ActionState<PlayerAction>, With<Ship>>,
+    sections: Query<&ShipSection>,
+    tractors: Query<(Entity, &GlobalTransform, &Transform, &Parent), With<ShipTractor>>,
+    parts: Query<&Parent, With<ShipPart>>,
+    bubbles: Query<(), With<ShieldBubble>>,
+    towed: Query<(Entity, &Towed)>,
+    towable: Query<
+        &GlobalTransform,
+        (
+            Or<(With<Asteroid>, With<Debris>, With<OrePickup>)>,
+            Without<ImpulseJoint>,
+        ),
+    >,
+) {
+    if gamestate.current() != &AppGameState::Game || playerstate.current() != &PlayerState::Flying {
+        return;
+    }
+    let ship_of = |body: Entity| sections.get(body).map_or(body, |section| section.ship);
+    for (tractor, global_transform, transform, parent) in tractors.iter() {
+        let ship = ship_of(parent.get());
+        let Ok(action_state) = ships.get(ship) else {
+            continue;
+        };
+        if !action_state.just_pressed(PlayerAction::Tractor) {
+            continue;
+        }
+        if let Some((load, _)) = towed.iter().find(|(_, towed)| towed.tractor == tractor) {
+            commands
+                .entity(load)
+                .remove::<ImpulseJoint>()
+                .remove::<Towed>();
+            continue;
+        }
+
+        let origin = global_transform.translation().truncate();
+        let direction = (global_transform.compute_transform().rotation * Vec3::Y).truncate();
+        // The beam goes through the parts of its own ship, and through shield bubbles
+        let not_own_part = |collider: Entity| {
+            !bubbles.contains(collider)
+                && parts
+                    .get(collider)
+                    .map_or(true, |part| ship_of(part.get()) != ship)
+        };
+        let filter = QueryFilter::new().predicate(&not_own_part);
+        let Some((collider, distance)) =
+            rapier_context.cast_ray(origin, direction, TRACTOR_RANGE, true, filter)
+        else {
+            continue;
+        };
+        // Debris parts are colliders of the debris body
+        let Some(load) = [Some(collider), rapier_context.collider_parent(collider)]
+            .into_iter()
+            .flatten()
+            .find(|entity| towable.contains(*entity))
+        else {
+            continue;
+        };
+        let Ok(load_transform) = towable.get(load) else {
+            continue;
+        };
+        let hit = origin + direction * distance;
+        let anchor = point_relative_to_transform(hit.extend(0.0), load_transform).truncate();
+        let hold = transform.translation.truncate() + Vec2::Y * distance.max(TRACTOR_MIN_HOLD);
+        let mut joint = GenericJointBuilder::new(JointAxesMask::empty())
+            .local_anchor1(hold)
+            .local_anchor2(anchor);
+        // A force based spring, so that heavy loads drag the ship around
+        for axis in [JointAxis::X, JointAxis::Y] {
+            joint = joint
+                .motor_model(axis, MotorModel::ForceBased)
+                .motor_position(axis, 0.0, TRACTOR_STIFFNESS, TRACTOR_DAMPING);
+        }
+        commands
+            .entity(load)
+            .insert((ImpulseJoint::new(parent.get(), joint), Towed { tractor }));
+    }
+}
+
+// Loads are dropped when their tractor is destroyed or respawned
+fn tractor_leash_system(
+    mut commands: Commands,
+    towed: Query<(Entity, &Towed)>,
+    tractors: Query<(), With<ShipTractor>>,
+) {
+    for (load, towed) in towed.iter() {
+        if !tractors.contains(towed.tractor) {
+            commands
+                .entity(load)
+                .remove::<ImpulseJoint>()
+                .remove::<Towed>();
+        }
+    }
+}
+
+// Ships are rebuilt in build mode, every load is dropped
+fn tractor_release_system(mut commands: Commands, towed: Query<Entity, With<Towed>>) {
+    for load in towed.iter() {
+        commands
+            .entity(load)
+            .remove::<ImpulseJoint>()
+            .remove::<Towed>();
+    }
+}
+
+fn tractor_beams_system(
+    towed: Query<(&Towed, &GlobalTransform)>,
+    tractors: Query<&GlobalTransform, With<ShipTractor>>,
+    mut beams: Query<&mut Path, With<TractorBeams>>,
+) {
+    let Ok(mut path) = beams.get_single_mut() else {
+        return;
+    };
+    let mut path_builder = PathBuilder::new();
+    for (towed, load_transform) in towed.iter() {
+        if let Ok(tractor_transform) = tractors.get(towed.tractor) {
+            path_builder.move_to(tractor_transform.translation().truncate());
+            path_builder.line_to(load_transform.translation().truncate());
+        }
+    }
+    *path = path_builder.build();
+}
@@ -0,0 +1,103 @@
+use crate::prelude::*;
+
+/// Half angle rad of the arc turrets aim within, around the ship forward direction
+pub const TURRET_ARC: f32 = 2.0;
+/// Turn rate rad/s of the turrets
+pub const TURRET_TURN_RATE: f32 = 4.0;
+/// Ore cost of a turret, on top of the cost of its weapon
+pub const TURRET_COST: u32 = 8;
+
+/// How the turrets of a ship pick what they aim at
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TurretMode {
+    /// Aim at the mouse cursor
+    Cursor,
+    /// Aim at the nearest hostile within reach of the weapon
+    Auto,
+}
+
+pub struct TurretPlugin;
+
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_update(AppState::Game)
+                .with_system(turret_mode_system)
+                .with_system(turret_aim_system.after(turret_mode_system)),
+        );
+    }
+}
+
+fn turret_mode_system(mut ships: Query<(&ActionState<PlayerAction>, &mut TurretMode)>) {
+    for (action_state, mut mode) in ships.iter_mut() {
+        if action_state.just_pressed(PlayerAction::ToggleTurretMode) {
+            *mode = match *mode {
+                TurretMode::Cursor => TurretMode::Auto,
+                TurretMode::Auto => TurretMode::Cursor,
+            };
+        }
+    }
+}
+
+// Turn every turret toward its aim point, as far as its arc allows.
+// Turrets without anything to aim at swing back forward.
+fn turret_aim_system(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    factions: Factions,
+    ships: Query<(Entity, &Transform, Option<&TurretMode>), With<Ship>>,
+    asteroids: Query<&Transform, With<Asteroid>>,
+    mut turrets: Query<
+        (
+            &mut ShipTurret,
+            &mut Transform,
+            &GlobalTransform,
+            &ShipWeapon,
+            &Parent,
+        ),
+        (Without<Ship>, Without<Asteroid>),
+    >,
+) {
+    let cursor = get_cursor_position(windows, camera).map(|cursor| cursor.truncate());
+    for (mut turret, mut transform, global_transform, weapon, parent) in turrets.iter_mut() {
+        let ship = factions.ship_of(parent.get());
+        let mode = ships
+            .get(ship)
+            .ok()
+            .and_then(|(_, _, mode)| mode.copied())
+            .unwrap_or(TurretMode::Auto);
+        let origin = global_transform.translation().truncate();
+        // Rotation of the body the turret is mounted on
+        let mount_rotation =
+            global_transform.compute_transform().rotation * transform.rotation.inverse();
+        let local_angle = |point: Vec2| {
+            Vec2::Y
+                .angle_between((mount_rotation.inverse() * (point - origin).extend(0.0)).truncate())
+        };
+
+        let target = match mode {
+            TurretMode::Cursor => cursor.map(local_angle),
+            TurretMode::Auto => {
+                let reach = weapon.kind.definition().reach();
+                let hostiles = asteroids.iter().chain(
+                    ships
+                        .iter()
+                        .filter(|(entity, _, _)| factions.hostile(ship, *entity))
+                        .map(|(_, transform, _)| transform),
+                );
+                hostiles
+                    .map(|transform| transform.translation.truncate())
+                    .filter(|position| position.distance(origin) <= reach)
+                    .filter(|position| local_angle(*position).abs() <= turret.arc)
+                    .min_by(|a, b| a.distance(origin).total_cmp(&b.distance(origin)))
+                    .map(local_angle)
+            }
+        };
+        let target = target.unwrap_or(0.0).clamp(-turret.arc, turret.arc);
+        // Arcs are narrower than a half turn, turrets never wrap around
+        let step = turret.turn_rate * time.delta_seconds();
+        turret.angle += (target - turret.angle).clamp(-step, step);
+        transform.rotation = Quat::from_rotation_z(turret.angle);
+    }
+}
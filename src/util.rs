@@ -1,3 +1,6 @@
+use bevy_rapier2d::rapier::math::Point;
+use bevy_rapier2d::rapier::prelude::RigidBodyHandle;
+
 use crate::prelude::*;
 
 struct Ray {
@@ -48,5 +51,45 @@ pub fn round_to_grid(pos: Vec2, grid_size: f32) -> Vec2 {
 }
 
 pub fn point_relative_to_transform(point: Vec3, transform: &GlobalTransform) -> Vec3 {
-    return transform.compute_matrix().inverse().transform_point3(point);
+    transform.compute_matrix().inverse().transform_point3(point)
+}
+
+pub fn point_segment_distance(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.length_squared();
+    if length_squared == 0.0 {
+        return point.distance(start);
+    }
+    let t = ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
+
+pub fn segment_distance(start1: Vec2, end1: Vec2, start2: Vec2, end2: Vec2) -> f32 {
+    let d1 = end1 - start1;
+    let d2 = end2 - start2;
+    let side = |d: Vec2, origin: Vec2, point: Vec2| d.perp_dot(point - origin);
+    // Segments crossing each other
+    if side(d1, start1, start2) * side(d1, start1, end2) < 0.0
+        && side(d2, start2, start1) * side(d2, start2, end1) < 0.0
+    {
+        return 0.0;
+    }
+    point_segment_distance(start1, start2, end2)
+        .min(point_segment_distance(end1, start2, end2))
+        .min(point_segment_distance(start2, start1, end1))
+        .min(point_segment_distance(end2, start1, end1))
+}
+
+/// Velocity of a rigid body at a world point, including the contribution of its rotation
+pub fn body_velocity_at_point(
+    rapier_context: &RapierContext,
+    body: RigidBodyHandle,
+    point: Vec2,
+) -> Option<Vec2> {
+    let scale = rapier_context.physics_scale();
+    rapier_context.bodies.get(body).map(|body| {
+        let point = point / scale;
+        let velocity = body.velocity_at_point(&Point::new(point.x, point.y));
+        Vec2::new(velocity.x, velocity.y) * scale
+    })
 }
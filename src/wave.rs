@@ -0,0 +1,208 @@
+use std::f32::consts::PI;
+
+use crate::prelude::*;
+
+/// Duration s of the calm before the first wave
+pub const FIRST_WAVE_DELAY: f32 = 5.0;
+/// Duration s of the breather between two waves, time enough to rework the ship
+pub const BREATHER_DURATION: f32 = 12.0;
+/// Duration s the wave banner stays up once a wave starts
+pub const WAVE_BANNER_DURATION: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WavePhase {
+    /// Nothing spawns until the timer finishes
+    Breather,
+    /// Asteroids and enemies spawn until the wave is exhausted,
+    /// the wave ends once they are all cleared
+    Assault,
+}
+
+/// Schedules the waves, each one harder than the last
+#[derive(Debug, Resource)]
+pub struct WaveDirector {
+    /// Current wave, or last cleared during a breather
+    pub wave: u32,
+    pub phase: WavePhase,
+    /// Breather countdown, or delay between spawns during an assault
+    pub timer: Timer,
+    /// Asteroids left to spawn in the current wave
+    pub pending: u32,
+    /// Enemy ships left to spawn in the current wave
+    pub pending_enemies: u32,
+    /// Game time s, pauses excluded
+    pub elapsed: f32,
+    /// Game time s the current wave started at
+    pub wave_start: f32,
+}
+
+impl WaveDirector {
+    fn new() -> Self {
+        WaveDirector {
+            wave: 0,
+            phase: WavePhase::Breather,
+            timer: Timer::from_seconds(FIRST_WAVE_DELAY, TimerMode::Once),
+            pending: 0,
+            pending_enemies: 0,
+            elapsed: 0.0,
+            wave_start: 0.0,
+        }
+    }
+
+    /// Grows with the wave number, the score and the time survived
+    pub fn difficulty(&self, score: u32) -> f32 {
+        self.wave as f32 + score as f32 / 500.0 + self.elapsed / 60.0
+    }
+
+    /// Seconds left before the next wave, during a breather
+    pub fn countdown(&self) -> Option<f32> {
+        (self.phase == WavePhase::Breather).then(|| self.timer.remaining_secs())
+    }
+}
+
+/// Content of a wave, derived from the difficulty when it starts
+struct WavePlan {
+    asteroids: u32,
+    enemies: u32,
+    /// Delay s between two spawns
+    interval: f32,
+    /// Speed multiplier of the spawned asteroids
+    speed: f32,
+    /// Chance of an asteroid being big, others are medium
+    big_chance: f64,
+}
+
+impl WavePlan {
+    fn new(difficulty: f32) -> Self {
+        WavePlan {
+            asteroids: 2 + (difficulty * 1.5) as u32,
+            // Enemy ships join from the third wave on
+            enemies: (difficulty / 3.0) as u32,
+            interval: (5.0 / (1.0 + difficulty * 0.25)).max(0.8),
+            speed: 1.0 + difficulty * 0.1,
+            big_chance: (0.3 + difficulty as f64 * 0.1).min(0.9),
+        }
+    }
+}
+
+pub struct WavePlugin;
+
+impl Plugin for WavePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(AppState::Game).with_system(wave_director_spawn))
+            .add_system_set(SystemSet::on_update(AppState::Game).with_system(wave_director_system));
+    }
+}
+
+fn wave_director_spawn(mut commands: Commands) {
+    commands.insert_resource(WaveDirector::new());
+}
+
+// Alternate breathers and waves of asteroids and enemy ships thrown from the arena edges
+fn wave_director_system(
+    time: Res<Time>,
+    gamestate: Res<State<AppGameState>>,
+    arena: Res<Arena>,
+    mut director: ResMut<WaveDirector>,
+    mut asteroid_spawn_events: EventWriter<AsteroidSpawnEvent>,
+    mut enemy_spawn_events: EventWriter<EnemySpawnEvent>,
+    asteroids: Query<&Asteroid>,
+    enemies: Query<(), With<Enemy>>,
+) {
+    if gamestate.current() != &AppGameState::Game {
+        return;
+    }
+    director.elapsed += time.delta_seconds();
+    director.timer.tick(time.delta());
+    match director.phase {
+        WavePhase::Breather => {
+            if director.timer.finished() {
+                director.wave += 1;
+                director.wave_start = director.elapsed;
+                let plan = WavePlan::new(director.difficulty(arena.score));
+                director.phase = WavePhase::Assault;
+                director.pending = plan.asteroids;
+                director.pending_enemies = plan.enemies;
+                director.timer = Timer::from_seconds(plan.interval, TimerMode::Repeating);
+                // The first asteroid comes with the banner
+                let interval = director.timer.duration();
+                director.timer.set_elapsed(interval);
+            }
+        }
+        WavePhase::Assault => {
+            let count = asteroids.iter().count();
+            if director.pending == 0 && director.pending_enemies == 0 {
+                if count == 0 && enemies.is_empty() {
+                    director.phase = WavePhase::Breather;
+                    director.timer = Timer::from_seconds(BREATHER_DURATION, TimerMode::Once);
+                }
+                return;
+            }
+            if !director.timer.just_finished() {
+                return;
+            }
+            // Enemies are scattered among the asteroids of the wave
+            let mut rng = thread_rng();
+            let pending = director.pending + director.pending_enemies;
+            if rng.gen_range(0..pending) < director.pending_enemies {
+                director.pending_enemies -= 1;
+                let position = edge_position();
+                enemy_spawn_events.send(EnemySpawnEvent {
+                    position,
+                    velocity: -position.normalize_or_zero() * 60.0,
+                });
+            } else if count < MAX_ASTEROIDS {
+                director.pending -= 1;
+                let plan = WavePlan::new(director.difficulty(arena.score));
+                asteroid_spawn_events.send(edge_asteroid(&plan));
+            }
+        }
+    }
+}
+
+// A random point on the arena edges
+fn edge_position() -> Vec2 {
+    let mut rng = thread_rng();
+    let half_width = ARENA_WIDTH / 2.0;
+    let half_height = ARENA_HEIGHT / 2.0;
+    if rng.gen_bool(0.5) {
+        Vec2::new(
+            if rng.gen_bool(0.5) {
+                -half_width
+            } else {
+                half_width
+            },
+            rng.gen_range(-half_height..half_height),
+        )
+    } else {
+        Vec2::new(
+            rng.gen_range(-half_width..half_width),
+            if rng.gen_bool(0.5) {
+                -half_height
+            } else {
+                half_height
+            },
+        )
+    }
+}
+
+// An asteroid entering from a random arena edge, aimed roughly toward the center
+fn edge_asteroid(plan: &WavePlan) -> AsteroidSpawnEvent {
+    let mut rng = thread_rng();
+    let position = edge_position();
+    let direction = Vec2::from_angle(rng.gen_range(-PI / 4.0..PI / 4.0)).rotate(-position);
+    let size = if rng.gen_bool(plan.big_chance) {
+        AsteroidSize::Big
+    } else {
+        AsteroidSize::Medium
+    };
+    AsteroidSpawnEvent {
+        polygon: asteroid_polygon(size.radius()),
+        position,
+        rotation: Quat::IDENTITY,
+        velocity: Velocity {
+            linvel: direction.normalize_or_zero() * rng.gen_range(30.0..80.0) * plan.speed,
+            angvel: rng.gen_range(-1.0..1.0) * plan.speed,
+        },
+    }
+}
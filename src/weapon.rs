@@ -0,0 +1,233 @@
+use crate::prelude::*;
+
+/// Characteristics shared by every weapon mount of a kind
+#[derive(Debug)]
+pub struct WeaponDefinition {
+    pub name: &'static str,
+    /// Projectile speed px/s
+    pub speed: f32,
+    /// Duration s before a projectile vanishes
+    pub lifetime: f32,
+    /// Damage dealt by each projectile
+    pub damage: u32,
+    /// Angle rad the projectiles of a shot scatter within
+    pub spread: f32,
+    /// Projectiles fired per shot
+    pub burst: u32,
+    /// Delay s between two shots
+    pub interval: f32,
+    /// Heat generated on every shot, or per second of firing for beams
+    pub heat: f32,
+    /// Power W drawn while firing
+    pub power: f32,
+    /// Ore cost of building the mount
+    pub cost: u32,
+    /// Projectile size px
+    pub size: Vec2,
+    pub sprite: ProjectileSprite,
+    /// Tint of the projectile sprite
+    pub color: Color,
+    pub sound: WeaponSound,
+    /// Guidance of self propelled projectiles
+    pub missile: Option<MissileDefinition>,
+    /// Continuous hitscan beam, fired instead of projectiles
+    pub beam: Option<BeamDefinition>,
+}
+
+impl WeaponDefinition {
+    /// Distance px the weapon hits at, at most
+    pub fn reach(&self) -> f32 {
+        self.beam
+            .as_ref()
+            .map_or(self.speed * self.lifetime, |beam| beam.range)
+    }
+}
+
+/// Characteristics of a continuous beam
+#[derive(Debug)]
+pub struct BeamDefinition {
+    /// Reach px of the beam
+    pub range: f32,
+    /// Damage dealt per second to what the beam hits
+    pub damage_rate: f32,
+}
+
+/// Characteristics of a homing missile
+#[derive(Debug)]
+pub struct MissileDefinition {
+    /// Acceleration px/s2 of its engine
+    pub acceleration: f32,
+    /// Top speed px/s
+    pub max_speed: f32,
+    /// Turn rate rad/s
+    pub turn_rate: f32,
+    /// Half angle rad of the cone targets are looked for in
+    pub seek_cone: f32,
+    /// Distance px targets are looked for within
+    pub seek_range: f32,
+    /// Distance px to its target the missile detonates at
+    pub fuse_radius: f32,
+    /// Radius px of the blast
+    pub blast_radius: f32,
+    /// Damage dealt to everything caught in the blast
+    pub blast_damage: u32,
+}
+
+pub const LASER: WeaponDefinition = WeaponDefinition {
+    name: "laser",
+    speed: 500.0,
+    lifetime: 2.0,
+    damage: 1,
+    spread: 0.0,
+    burst: 1,
+    interval: 0.2,
+    heat: 12.0,
+    power: 30.0,
+    cost: 10,
+    size: Vec2::new(5.0, 20.0),
+    sprite: ProjectileSprite::Laser,
+    color: Color::WHITE,
+    sound: WeaponSound::Laser,
+    missile: None,
+    beam: None,
+};
+
+pub const SHOTGUN: WeaponDefinition = WeaponDefinition {
+    name: "shotgun",
+    speed: 400.0,
+    lifetime: 0.6,
+    damage: 1,
+    spread: 0.5,
+    burst: 6,
+    interval: 0.8,
+    heat: 25.0,
+    power: 40.0,
+    cost: 14,
+    size: Vec2::new(4.0, 8.0),
+    sprite: ProjectileSprite::Flare,
+    color: Color::rgb(1.0, 0.7, 0.3),
+    sound: WeaponSound::Blast,
+    missile: None,
+    beam: None,
+};
+
+pub const SNIPER: WeaponDefinition = WeaponDefinition {
+    name: "sniper",
+    speed: 900.0,
+    lifetime: 1.5,
+    damage: 4,
+    spread: 0.0,
+    burst: 1,
+    interval: 1.5,
+    heat: 30.0,
+    power: 50.0,
+    cost: 18,
+    size: Vec2::new(4.0, 30.0),
+    sprite: ProjectileSprite::Laser,
+    color: Color::rgb(0.5, 0.8, 1.0),
+    sound: WeaponSound::Laser,
+    missile: None,
+    beam: None,
+};
+
+pub const BLASTER: WeaponDefinition = WeaponDefinition {
+    name: "blaster",
+    speed: 600.0,
+    lifetime: 1.0,
+    damage: 1,
+    spread: 0.08,
+    burst: 1,
+    interval: 0.08,
+    heat: 5.0,
+    power: 35.0,
+    cost: 14,
+    size: Vec2::new(4.0, 12.0),
+    sprite: ProjectileSprite::Flare,
+    color: Color::rgb(1.0, 1.0, 0.4),
+    sound: WeaponSound::Laser,
+    missile: None,
+    beam: None,
+};
+
+pub const MISSILE: WeaponDefinition = WeaponDefinition {
+    name: "missile",
+    speed: 150.0,
+    lifetime: 4.0,
+    damage: 1,
+    spread: 0.0,
+    burst: 1,
+    interval: 1.2,
+    heat: 20.0,
+    power: 40.0,
+    cost: 20,
+    size: Vec2::new(6.0, 14.0),
+    sprite: ProjectileSprite::Laser,
+    color: Color::rgb(1.0, 0.4, 0.2),
+    sound: WeaponSound::Blast,
+    missile: Some(MissileDefinition {
+        acceleration: 400.0,
+        max_speed: 450.0,
+        turn_rate: 3.0,
+        seek_cone: 0.8,
+        seek_range: 500.0,
+        fuse_radius: 30.0,
+        blast_radius: 50.0,
+        blast_damage: 2,
+    }),
+    beam: None,
+};
+
+pub const BEAM: WeaponDefinition = WeaponDefinition {
+    name: "beam",
+    speed: 0.0,
+    lifetime: 0.0,
+    damage: 0,
+    spread: 0.0,
+    burst: 0,
+    interval: 0.0,
+    heat: 30.0,
+    power: 45.0,
+    cost: 22,
+    size: Vec2::ZERO,
+    sprite: ProjectileSprite::Laser,
+    color: Color::rgb(0.4, 1.0, 1.0),
+    sound: WeaponSound::Laser,
+    missile: None,
+    beam: Some(BeamDefinition {
+        range: 300.0,
+        damage_rate: 3.0,
+    }),
+};
+
+/// Weapon fired by a mount, see its definition
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponKind {
+    Laser,
+    Shotgun,
+    Sniper,
+    Blaster,
+    Missile,
+    Beam,
+}
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 6] = [
+        WeaponKind::Laser,
+        WeaponKind::Shotgun,
+        WeaponKind::Sniper,
+        WeaponKind::Blaster,
+        WeaponKind::Missile,
+        WeaponKind::Beam,
+    ];
+
+    pub fn definition(&self) -> &'static WeaponDefinition {
+        match self {
+            WeaponKind::Laser => &LASER,
+            WeaponKind::Shotgun => &SHOTGUN,
+            WeaponKind::Sniper => &SNIPER,
+            WeaponKind::Blaster => &BLASTER,
+            WeaponKind::Missile => &MISSILE,
+            WeaponKind::Beam => &BEAM,
+        }
+    }
+}